@@ -0,0 +1,41 @@
+//! Connects to a running spectator feed and prints every frame.
+//!
+//!     cargo run -- --spectator 9001
+//!     cargo run --example spectator_client -- ws://127.0.0.1:9001
+
+use pong::spectator::{StateFrame, DEFAULT_PORT};
+use tungstenite::Message;
+
+fn main() {
+    let url = std::env::args().nth(1)
+        .unwrap_or_else(|| format!("ws://127.0.0.1:{}", DEFAULT_PORT));
+
+    let (mut socket, _) = match tungstenite::connect(&url) {
+        Ok(connection) => connection,
+        Err(error) => {
+            eprintln!("Could not connect to {}: {}", url, error);
+            std::process::exit(1);
+        },
+    };
+
+    loop {
+        let text = match socket.read() {
+            Ok(Message::Text(text)) => text,
+            Ok(Message::Close(_)) | Err(_) => break,
+            Ok(_) => continue,
+        };
+
+        match serde_json::from_str::<StateFrame>(&text) {
+            Ok(frame) => println!(
+                "tick {:>6}  {:<8} {:<12} {} - {}  ball {}",
+                frame.tick,
+                frame.game_state,
+                frame.match_state.unwrap_or_default(),
+                frame.score.0,
+                frame.score.1,
+                frame.ball.map(|ball| format!("({:.0}, {:.0})", ball.x, ball.y)).unwrap_or_default(),
+            ),
+            Err(error) => eprintln!("Unexpected message {:?}: {}", text, error),
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,426 @@
+use bevy::prelude::*;
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::game::settings::{GameMode, GameSettings, PlayerType};
+use crate::game::states::GameState;
+use crate::pong::events::{MatchEnded, MatchStarted, PointScored};
+use crate::pong::{Arena, GameRng, MatchStats, Rules};
+
+/// Headless matches that run longer than this are ended and reported as unfinished.
+const MAX_MATCH_SECONDS: f32 = 600.;
+
+pub const USAGE: &str = "\
+Usage: pong [options]
+
+  --p1 <human|ai:easy|ai:difficult|ai:impossible>
+  --p2 <human|ai:easy|ai:difficult|ai:impossible>
+  --target <points>                 points needed to win a match
+  --seed <number>                   seed for gameplay randomness
+  --arena <classic|wide|compact>
+  --headless                        simulate without a window and print results as JSON
+  --terminal                        play in the terminal instead of a window
+  --matches <count>                 matches to play back to back (headless only)
+  --replay-out <dir>                write a log of every match (headless only)
+  --spectator <port>                broadcast the match state over WebSocket on localhost
+  --spectator-every <ticks>         physics ticks between spectator frames
+  --calibrate <matches>             play every AI difficulty pairing and print a report
+  --format <json|csv>               calibration report format
+  --help";
+
+#[derive(Default, Clone, Copy, PartialEq, Debug)]
+pub enum ArenaPreset {
+    #[default]
+    Classic,
+    Wide,
+    Compact,
+}
+
+impl ArenaPreset {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ArenaPreset::Classic => "classic",
+            ArenaPreset::Wide => "wide",
+            ArenaPreset::Compact => "compact",
+        }
+    }
+
+    pub fn size(&self) -> Vec2 {
+        match self {
+            ArenaPreset::Classic => Vec2::new(1280., 720.),
+            ArenaPreset::Wide => Vec2::new(1600., 720.),
+            ArenaPreset::Compact => Vec2::new(960., 600.),
+        }
+    }
+}
+
+impl FromStr for ArenaPreset {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "classic" => Ok(ArenaPreset::Classic),
+            "wide" => Ok(ArenaPreset::Wide),
+            "compact" => Ok(ArenaPreset::Compact),
+            _ => Err(format!("Unknown arena {}", name)),
+        }
+    }
+}
+
+#[derive(Default, Clone, Copy, PartialEq, Debug)]
+pub enum ReportFormat {
+    #[default]
+    Json,
+    Csv,
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "json" => Ok(ReportFormat::Json),
+            "csv" => Ok(ReportFormat::Csv),
+            _ => Err(format!("Unknown format {}", name)),
+        }
+    }
+}
+
+#[derive(Default, Clone, PartialEq, Debug)]
+pub struct LaunchOptions {
+    pub player1: Option<PlayerType>,
+    pub player2: Option<PlayerType>,
+    pub target_score: Option<u32>,
+    pub seed: Option<u64>,
+    pub arena: Option<ArenaPreset>,
+    pub headless: bool,
+    pub terminal: bool,
+    pub matches: Option<u32>,
+    pub replay_out: Option<PathBuf>,
+    pub spectator: Option<u16>,
+    pub spectator_every: Option<u32>,
+    pub calibrate: Option<u32>,
+    pub format: Option<ReportFormat>,
+    pub help: bool,
+}
+
+fn parse_arg<T: FromStr>(args: &mut impl Iterator<Item = String>, name: &str) -> Result<T, String> {
+    let arg = args.next().ok_or_else(|| format!("Missing value for {}", name))?;
+    arg.parse().map_err(|_| format!("Invalid value {:?} for {}", arg, name))
+}
+
+impl LaunchOptions {
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut options = Self::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--p1" => options.player1 = Some(parse_arg(&mut args, &arg)?),
+                "--p2" => options.player2 = Some(parse_arg(&mut args, &arg)?),
+                "--target" => options.target_score = Some(parse_arg(&mut args, &arg)?),
+                "--seed" => options.seed = Some(parse_arg(&mut args, &arg)?),
+                "--arena" => options.arena = Some(parse_arg(&mut args, &arg)?),
+                "--headless" => options.headless = true,
+                "--terminal" => options.terminal = true,
+                "--matches" => options.matches = Some(parse_arg(&mut args, &arg)?),
+                "--replay-out" => options.replay_out = Some(parse_arg(&mut args, &arg)?),
+                "--spectator" => options.spectator = Some(parse_arg(&mut args, &arg)?),
+                "--spectator-every" => options.spectator_every = Some(parse_arg(&mut args, &arg)?),
+                "--calibrate" => options.calibrate = Some(parse_arg(&mut args, &arg)?),
+                "--format" => options.format = Some(parse_arg(&mut args, &arg)?),
+                "--help" | "-h" => options.help = true,
+                _ => return Err(format!("Unknown option {:?}", arg)),
+            }
+        }
+
+        options.validate()?;
+        Ok(options)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.target_score == Some(0) {
+            return Err("--target must be at least 1".to_string());
+        }
+        if self.matches == Some(0) {
+            return Err("--matches must be at least 1".to_string());
+        }
+        if self.calibrate.is_some() {
+            if self.calibrate == Some(0) {
+                return Err("--calibrate must be at least 1".to_string());
+            }
+            if self.player1.is_some() || self.player2.is_some() || self.headless || self.terminal
+                || self.matches.is_some() || self.replay_out.is_some() || self.spectator.is_some()
+            {
+                return Err("--calibrate plays every difficulty pairing on its own, \
+                    only --target, --seed, --arena and --format apply".to_string());
+            }
+            return Ok(());
+        }
+        if self.format.is_some() {
+            return Err("--format needs --calibrate".to_string());
+        }
+        if self.spectator_every == Some(0) {
+            return Err("--spectator-every must be at least 1".to_string());
+        }
+        if self.spectator_every.is_some() && self.spectator.is_none() {
+            return Err("--spectator-every needs --spectator".to_string());
+        }
+        if self.headless && self.terminal {
+            return Err("--headless and --terminal can't be combined".to_string());
+        }
+        if !self.headless && (self.matches.is_some() || self.replay_out.is_some()) {
+            return Err("--matches and --replay-out need --headless".to_string());
+        }
+
+        let (player1, player2) = self.players();
+        if self.headless && (player1 == PlayerType::Human || player2 == PlayerType::Human) {
+            return Err("--headless needs computer players, e.g. --p1 ai:easy --p2 ai:easy".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Whether to skip the main menu and go straight into a configured match.
+    pub fn skips_menu(&self) -> bool {
+        self.headless
+            || self.terminal
+            || self.player1.is_some()
+            || self.player2.is_some()
+            || self.target_score.is_some()
+            || self.seed.is_some()
+            || self.arena.is_some()
+    }
+
+    pub fn players(&self) -> (PlayerType, PlayerType) {
+        let defaults = GameSettings::default();
+        (
+            self.player1.unwrap_or(*defaults.get_player1()),
+            self.player2.unwrap_or(*defaults.get_player2()),
+        )
+    }
+
+    pub fn match_count(&self) -> usize {
+        self.matches.unwrap_or(1) as usize
+    }
+}
+
+#[derive(Serialize, Clone, Copy)]
+struct PointRecord {
+    time: f32,
+    scorer: usize,
+    score: (u32, u32),
+}
+
+#[derive(Serialize, Clone, Copy)]
+struct MatchResult {
+    winner: Option<usize>,
+    completed: bool,
+    score: (u32, u32),
+    duration: f32,
+    paddle_hits: (u32, u32),
+    wall_bounces: u32,
+    longest_rally: u32,
+}
+
+impl MatchResult {
+    fn new(ended: &MatchEnded, completed: bool) -> Self {
+        let MatchStats { paddle_hits, wall_bounces, longest_rally, duration, .. } = ended.stats;
+
+        Self {
+            winner: ended.winner.filter(|_| completed),
+            completed,
+            score: ended.score,
+            duration,
+            paddle_hits,
+            wall_bounces,
+            longest_rally,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct MatchSetup {
+    seed: Option<u64>,
+    player1: String,
+    player2: String,
+    target_score: u32,
+    arena: &'static str,
+}
+
+#[derive(Serialize)]
+struct RunReport<'a> {
+    #[serde(flatten)]
+    setup: &'a MatchSetup,
+    wins: (u32, u32),
+    unfinished: u32,
+    matches: &'a [MatchResult],
+}
+
+#[derive(Serialize)]
+struct MatchReplay<'a> {
+    #[serde(flatten)]
+    setup: &'a MatchSetup,
+    match_num: usize,
+    points: &'a [PointRecord],
+    result: &'a MatchResult,
+}
+
+#[derive(Resource)]
+struct MatchRun {
+    options: LaunchOptions,
+    started: f32,
+    points: Vec<PointRecord>,
+    results: Vec<MatchResult>,
+}
+
+impl MatchRun {
+    fn setup(&self, rules: &Rules) -> MatchSetup {
+        let (player1, player2) = self.options.players();
+
+        MatchSetup {
+            seed: self.options.seed,
+            player1: player1.to_string(),
+            player2: player2.to_string(),
+            target_score: rules.target_score,
+            arena: self.options.arena.unwrap_or_default().name(),
+        }
+    }
+
+    fn write_replay(&self, setup: &MatchSetup, result: &MatchResult) {
+        let Some(dir) = &self.options.replay_out else {
+            return;
+        };
+
+        let match_num = self.results.len() + 1;
+        let path = dir.join(format!("match-{:04}.json", match_num));
+        let replay = MatchReplay { setup, match_num, points: &self.points, result };
+
+        let written = fs::create_dir_all(dir)
+            .map_err(|error| error.to_string())
+            .and_then(|_| serde_json::to_string_pretty(&replay).map_err(|error| error.to_string()))
+            .and_then(|contents| fs::write(&path, contents).map_err(|error| error.to_string()));
+
+        if let Err(error) = written {
+            eprintln!("Could not write {}: {}", path.display(), error);
+        }
+    }
+
+    fn report(&self, setup: &MatchSetup) -> String {
+        let mut wins = (0, 0);
+        let mut unfinished = 0;
+        for result in &self.results {
+            match result.winner {
+                Some(1) => wins.0 += 1,
+                Some(_) => wins.1 += 1,
+                None => unfinished += 1,
+            }
+        }
+
+        let report = RunReport { setup, wins, unfinished, matches: &self.results };
+        serde_json::to_string_pretty(&report).unwrap_or_else(|error| format!("{{\"error\": {:?}}}", error.to_string()))
+    }
+}
+
+fn start_configured_match(
+    mut commands: Commands,
+    run: Res<MatchRun>,
+    mut settings: ResMut<GameSettings>,
+    mut rules: ResMut<Rules>,
+    mut arena: ResMut<Arena>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let (player1, player2) = run.options.players();
+    settings.set_mode(GameMode::Versus);
+    settings.update_players(1, player1);
+    settings.update_players(2, player2);
+
+    if let Some(target_score) = run.options.target_score {
+        rules.target_score = target_score;
+    }
+    if let Some(preset) = run.options.arena {
+        arena.size = Some(preset.size());
+    }
+    if let Some(seed) = run.options.seed {
+        commands.insert_resource(GameRng::new(seed));
+    }
+
+    next_state.set(GameState::Playing);
+}
+
+fn start_match_log(_trigger: Trigger<MatchStarted>, time: Res<Time>, mut run: ResMut<MatchRun>) {
+    run.started = time.elapsed_secs();
+    run.points.clear();
+}
+
+fn log_point(trigger: Trigger<PointScored>, time: Res<Time>, mut run: ResMut<MatchRun>) {
+    let time = time.elapsed_secs() - run.started;
+    run.points.push(PointRecord { time, scorer: trigger.scorer, score: trigger.score });
+}
+
+fn finish_match(
+    trigger: Trigger<MatchEnded>,
+    rules: Res<Rules>,
+    mut run: ResMut<MatchRun>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut exit: EventWriter<AppExit>,
+) {
+    let (player1, player2) = trigger.score;
+    let completed = player1.max(player2) >= rules.target_score;
+    let result = MatchResult::new(&trigger, completed);
+
+    let setup = run.setup(&rules);
+    run.write_replay(&setup, &result);
+    run.results.push(result);
+
+    if run.results.len() < run.options.match_count() {
+        next_state.set(GameState::Playing);
+    } else {
+        println!("{}", run.report(&setup));
+        exit.send(AppExit::Success);
+    }
+}
+
+/// Nobody wins a match that is cut off, whatever the score.
+fn end_stalled_match(stats: Res<MatchStats>, mut next_state: ResMut<NextState<GameState>>) {
+    if stats.duration > MAX_MATCH_SECONDS {
+        next_state.set(GameState::Endgame);
+    }
+}
+
+/// Applies command-line options, and in headless mode plays the requested
+/// matches and prints the results.
+pub struct LaunchPlugin {
+    options: LaunchOptions,
+}
+
+impl LaunchPlugin {
+    pub fn new(options: LaunchOptions) -> Self {
+        Self { options }
+    }
+}
+
+impl Plugin for LaunchPlugin {
+    fn build(&self, app: &mut App) {
+        if !self.options.skips_menu() {
+            return;
+        }
+
+        app.insert_resource(MatchRun {
+            options: self.options.clone(),
+            started: 0.,
+            points: Vec::new(),
+            results: Vec::new(),
+        })
+            .add_systems(Startup, start_configured_match);
+
+        if self.options.headless {
+            app.add_observer(start_match_log)
+                .add_observer(log_point)
+                .add_observer(finish_match)
+                .add_systems(Update, end_stalled_match.run_if(in_state(GameState::Playing)));
+        }
+    }
+}
\ No newline at end of file
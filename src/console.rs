@@ -0,0 +1,286 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use leafwing_input_manager::prelude::ActionState;
+use std::collections::VecDeque;
+use std::str::FromStr;
+
+use crate::game::commands::{ChangeGameStateCommand, SetTimeScaleCommand, UpdatePlayerCommand};
+use crate::game::controls::GameAction;
+use crate::game::states::GameState;
+use crate::pong::commands::{ResetBallCommand, SetBallSpeedCommand, SetScoreCommand, SetTargetScoreCommand};
+
+const TOGGLE_CONSOLE: KeyCode = KeyCode::Backquote;
+const MAX_OUTPUT: usize = 50;
+
+const USAGE: [&str; 8] = [
+    "score <player1> <player2>",
+    "ball speed <speed>",
+    "ball reset",
+    "difficulty <player> <human|easy|difficult|impossible>",
+    "state <main|playing|endgame>",
+    "timescale <scale>",
+    "rules target <points>",
+    "help",
+];
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConsoleCommand {
+    Score(SetScoreCommand),
+    BallSpeed(SetBallSpeedCommand),
+    BallReset(ResetBallCommand),
+    Difficulty(UpdatePlayerCommand),
+    State(ChangeGameStateCommand),
+    TimeScale(SetTimeScaleCommand),
+    RulesTarget(SetTargetScoreCommand),
+}
+
+fn parse_arg<T: FromStr>(arg: Option<&str>, name: &str) -> Result<T, String> {
+    let arg = arg.ok_or_else(|| format!("Missing {}", name))?;
+    arg.parse().map_err(|_| format!("Invalid {} {:?}", name, arg))
+}
+
+fn parse_game_state(name: &str) -> Result<GameState, String> {
+    match name {
+        "main" => Ok(GameState::Main),
+        "playing" => Ok(GameState::Playing),
+        "endgame" => Ok(GameState::Endgame),
+        _ => Err(format!("Unknown state {:?}", name)),
+    }
+}
+
+impl FromStr for ConsoleCommand {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let mut words = line.split_whitespace();
+
+        let command = match (words.next(), words.next()) {
+            (Some("score"), player1) => ConsoleCommand::Score(SetScoreCommand::new(
+                parse_arg(player1, "score")?,
+                parse_arg(words.next(), "score")?,
+            )),
+            (Some("ball"), Some("speed")) => ConsoleCommand::BallSpeed(SetBallSpeedCommand::new(
+                parse_arg(words.next(), "speed")?,
+            )),
+            (Some("ball"), Some("reset")) => ConsoleCommand::BallReset(ResetBallCommand),
+            (Some("difficulty"), player_num) => {
+                let player_num = parse_arg(player_num, "player")?;
+                if !(1..=2).contains(&player_num) {
+                    return Err(format!("Invalid player {}", player_num));
+                }
+                ConsoleCommand::Difficulty(UpdatePlayerCommand::new(player_num, parse_arg(words.next(), "difficulty")?))
+            },
+            (Some("state"), Some(state)) => ConsoleCommand::State(ChangeGameStateCommand::new(parse_game_state(state)?)),
+            (Some("timescale"), scale) => ConsoleCommand::TimeScale(SetTimeScaleCommand::new(parse_arg(scale, "scale")?)),
+            (Some("rules"), Some("target")) => ConsoleCommand::RulesTarget(SetTargetScoreCommand::new(
+                parse_arg(words.next(), "target")?,
+            )),
+            (Some(command), _) => return Err(format!("Unknown command {:?}, try \"help\"", command)),
+            (None, _) => return Err("Empty command".to_string()),
+        };
+
+        match words.next() {
+            Some(extra) => Err(format!("Unexpected argument {:?}", extra)),
+            None => Ok(command),
+        }
+    }
+}
+
+impl Command for ConsoleCommand {
+    fn apply(self, world: &mut World) {
+        match self {
+            ConsoleCommand::Score(command) => command.apply(world),
+            ConsoleCommand::BallSpeed(command) => command.apply(world),
+            ConsoleCommand::BallReset(command) => command.apply(world),
+            ConsoleCommand::Difficulty(command) => command.apply(world),
+            ConsoleCommand::State(command) => command.apply(world),
+            ConsoleCommand::TimeScale(command) => command.apply(world),
+            ConsoleCommand::RulesTarget(command) => command.apply(world),
+        }
+    }
+}
+
+fn completions(words: &[&str]) -> &'static [&'static str] {
+    match words {
+        [] => &["score", "ball", "difficulty", "state", "timescale", "rules", "help"],
+        ["ball"] => &["speed", "reset"],
+        ["rules"] => &["target"],
+        ["state"] => &["main", "playing", "endgame"],
+        ["difficulty"] => &["1", "2"],
+        ["difficulty", _] => &["human", "easy", "difficult", "impossible"],
+        _ => &[],
+    }
+}
+
+pub fn complete(input: &str) -> String {
+    let mut words: Vec<&str> = input.split_whitespace().collect();
+    let partial = if input.ends_with(' ') || input.is_empty() { "" } else { words.pop().unwrap_or_default() };
+
+    let matches: Vec<&str> = completions(&words).iter()
+        .copied()
+        .filter(|candidate| candidate.starts_with(partial))
+        .collect();
+
+    match matches.as_slice() {
+        [completion] => {
+            words.push(completion);
+            format!("{} ", words.join(" "))
+        },
+        _ => input.to_string(),
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct Console {
+    open: bool,
+    input: String,
+    history: Vec<String>,
+    history_index: Option<usize>,
+    output: VecDeque<String>,
+}
+
+impl Console {
+    fn print(&mut self, line: impl Into<String>) {
+        if self.output.len() == MAX_OUTPUT {
+            self.output.pop_front();
+        }
+        self.output.push_back(line.into());
+    }
+
+    fn submit(&mut self, commands: &mut Commands) {
+        let line = std::mem::take(&mut self.input).trim().to_string();
+        self.history_index = None;
+        if line.is_empty() {
+            return;
+        }
+
+        self.print(format!("> {}", line));
+        if self.history.last() != Some(&line) {
+            self.history.push(line.clone());
+        }
+
+        if line == "help" {
+            USAGE.iter().for_each(|usage| self.print(*usage));
+            return;
+        }
+
+        match line.parse::<ConsoleCommand>() {
+            Ok(command) => commands.queue(command),
+            Err(error) => self.print(error),
+        }
+    }
+
+    fn browse_history(&mut self, older: bool) {
+        if self.history.is_empty() {
+            return;
+        }
+
+        let last = self.history.len() - 1;
+        self.history_index = match (self.history_index, older) {
+            (None, true) => Some(last),
+            (None, false) => None,
+            (Some(index), true) => Some(index.saturating_sub(1)),
+            (Some(index), false) if index < last => Some(index + 1),
+            (Some(_), false) => None,
+        };
+
+        self.input = self.history_index
+            .map(|index| self.history[index].clone())
+            .unwrap_or_default();
+    }
+}
+
+fn toggle_console(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut console: ResMut<Console>,
+    action_state: Option<ResMut<ActionState<GameAction>>>,
+) {
+    if !keys.just_pressed(TOGGLE_CONSOLE) {
+        return;
+    }
+
+    console.open = !console.open;
+    console.input.retain(|character| character != '`');
+
+    // Typing (and browsing history with the arrow keys) must not move the paddles
+    if let Some(mut action_state) = action_state {
+        if console.open {
+            action_state.disable();
+        } else {
+            action_state.enable();
+        }
+    }
+}
+
+fn show_console(mut commands: Commands, mut contexts: EguiContexts, mut console: ResMut<Console>) {
+    if !console.open {
+        return;
+    }
+
+    egui::TopBottomPanel::top("console").show(contexts.ctx_mut(), |ui| {
+        egui::ScrollArea::vertical()
+            .max_height(200.)
+            .stick_to_bottom(true)
+            .show(ui, |ui| {
+                for line in &console.output {
+                    ui.monospace(line);
+                }
+            });
+
+        let (tab, up, down) = ui.input_mut(|input| (
+            input.consume_key(egui::Modifiers::NONE, egui::Key::Tab),
+            input.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp),
+            input.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown),
+        ));
+
+        if tab {
+            console.input = complete(&console.input);
+        }
+        if up || down {
+            console.browse_history(up);
+        }
+
+        let response = ui.add(
+            egui::TextEdit::singleline(&mut console.input)
+                .font(egui::TextStyle::Monospace)
+                .desired_width(f32::INFINITY)
+                .lock_focus(true),
+        );
+
+        if response.lost_focus() && ui.input(|input| input.key_pressed(egui::Key::Enter)) {
+            console.submit(&mut commands);
+        }
+        response.request_focus();
+    });
+}
+
+#[derive(Clone)]
+pub struct ConsolePlugin {
+    install_egui: bool,
+}
+
+impl Default for ConsolePlugin {
+    fn default() -> Self {
+        Self { install_egui: true }
+    }
+}
+
+impl ConsolePlugin {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn without_egui(mut self) -> Self {
+        self.install_egui = false;
+        self
+    }
+}
+
+impl Plugin for ConsolePlugin {
+    fn build(&self, app: &mut App) {
+        crate::add_egui_if_missing(app, self.install_egui);
+
+        app.init_resource::<Console>()
+            .add_systems(Update, (toggle_console, show_console).chain());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,177 @@
+use bevy::prelude::*;
+use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+use bevy_egui::{egui, EguiContexts};
+use bevy_rapier2d::prelude::*;
+use std::collections::VecDeque;
+
+use crate::game::settings::PlayerType;
+use crate::game::states::{GameState, MatchState, MenuScreen, PausedState};
+use crate::pong::events::{BallEnteredGoal, BallHitPaddle, BallHitWall};
+use crate::pong::{Ball, Score, ScoreField, MAX_BALL_SPEED};
+
+const MAX_COLLISIONS: usize = 8;
+const TOGGLE_OVERLAY: KeyCode = KeyCode::F3;
+const TOGGLE_COLLIDERS: KeyCode = KeyCode::F4;
+
+#[derive(Resource, Default)]
+pub struct DebugOverlay {
+    visible: bool,
+    collisions: VecDeque<String>,
+}
+
+impl DebugOverlay {
+    fn record(&mut self, collision: String) {
+        if self.collisions.len() == MAX_COLLISIONS {
+            self.collisions.pop_front();
+        }
+        self.collisions.push_back(collision);
+    }
+}
+
+fn toggle_debug(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut overlay: ResMut<DebugOverlay>,
+    mut render_context: ResMut<DebugRenderContext>,
+) {
+    if keys.just_pressed(TOGGLE_OVERLAY) {
+        overlay.visible = !overlay.visible;
+    }
+    if keys.just_pressed(TOGGLE_COLLIDERS) {
+        render_context.enabled = !render_context.enabled;
+    }
+}
+
+fn side(field: Option<&ScoreField>) -> &'static str {
+    match field {
+        Some(ScoreField::Left) => "left",
+        Some(ScoreField::Right) => "right",
+        None => "unknown",
+    }
+}
+
+fn record_collisions(
+    mut paddle_hits: EventReader<BallHitPaddle>,
+    mut wall_hits: EventReader<BallHitWall>,
+    mut goals_entered: EventReader<BallEnteredGoal>,
+    mut overlay: ResMut<DebugOverlay>,
+    time: Res<Time>,
+    fields: Query<&ScoreField>,
+) {
+    let elapsed = time.elapsed_secs();
+
+    for hit in paddle_hits.read() {
+        overlay.record(format!(
+            "{:.2}s ball {} hit {} paddle, offset {:.2}",
+            elapsed, hit.ball, side(fields.get(hit.paddle).ok()), hit.offset,
+        ));
+    }
+    for hit in wall_hits.read() {
+        overlay.record(format!("{:.2}s ball {} hit wall {}", elapsed, hit.ball, hit.wall));
+    }
+    for goal in goals_entered.read() {
+        overlay.record(format!(
+            "{:.2}s ball {} entered {} goal",
+            elapsed, goal.ball, side(fields.get(goal.goal).ok()),
+        ));
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn show_overlay(
+    mut contexts: EguiContexts,
+    overlay: Res<DebugOverlay>,
+    diagnostics: Res<DiagnosticsStore>,
+    fixed_time: Res<Time<Fixed>>,
+    game_state: Res<State<GameState>>,
+    paused_state: Res<State<PausedState>>,
+    match_state: Option<Res<State<MatchState>>>,
+    menu_screen: Res<State<MenuScreen>>,
+    score: Res<Score>,
+    balls: Query<(&Transform, &Velocity), With<Ball>>,
+    paddles: Query<(&Transform, &ScoreField), With<PlayerType>>,
+) {
+    if !overlay.visible {
+        return;
+    }
+
+    let fps = diagnostics.get(&FrameTimeDiagnosticsPlugin::FPS)
+        .and_then(|fps| fps.smoothed())
+        .unwrap_or_default();
+
+    egui::Window::new("Debug").default_pos((10., 10.)).show(contexts.ctx_mut(), |ui| {
+        ui.label(format!("FPS: {:.0}", fps));
+        ui.label(format!(
+            "Fixed tick: {:.1} ms (overstep {:.1} ms)",
+            fixed_time.timestep().as_secs_f64() * 1000.,
+            fixed_time.overstep().as_secs_f64() * 1000.,
+        ));
+
+        ui.separator();
+        ui.label(format!("Game state: {:?}", game_state.get()));
+        ui.label(format!("Paused state: {:?}", paused_state.get()));
+        if let Some(match_state) = match_state {
+            ui.label(format!("Match state: {:?}", match_state.get()));
+        }
+        ui.label(format!("Menu screen: {:?}", menu_screen.get()));
+        ui.label(format!("Score: {}", score.display_text()));
+
+        ui.separator();
+        for (transform, velocity) in &balls {
+            ui.label(format!("Ball position: {:.0}, {:.0}", transform.translation.x, transform.translation.y));
+            ui.label(format!("Ball velocity: {:.0}, {:.0}", velocity.linvel.x, velocity.linvel.y));
+            ui.label(format!("Ball speed: {:.0} / {:.0}", velocity.linvel.length(), MAX_BALL_SPEED));
+        }
+        for (transform, field) in &paddles {
+            let side = match field {
+                ScoreField::Left => "Left",
+                ScoreField::Right => "Right",
+            };
+            ui.label(format!("{} paddle: {:.0}, {:.0}", side, transform.translation.x, transform.translation.y));
+        }
+
+        ui.separator();
+        ui.label("Collisions:");
+        for collision in overlay.collisions.iter().rev() {
+            ui.label(collision);
+        }
+    });
+}
+
+#[derive(Clone)]
+pub struct DebugOverlayPlugin {
+    install_egui: bool,
+}
+
+impl Default for DebugOverlayPlugin {
+    fn default() -> Self {
+        Self { install_egui: true }
+    }
+}
+
+impl DebugOverlayPlugin {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn without_egui(mut self) -> Self {
+        self.install_egui = false;
+        self
+    }
+}
+
+impl Plugin for DebugOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        crate::add_egui_if_missing(app, self.install_egui);
+
+        app.add_plugins((
+            FrameTimeDiagnosticsPlugin,
+            RapierDebugRenderPlugin::default().disabled(),
+        ))
+            .init_resource::<DebugOverlay>()
+            .add_systems(Update, (
+                toggle_debug,
+                record_collisions,
+                show_overlay,
+            ));
+    }
+}
\ No newline at end of file
@@ -2,19 +2,58 @@ pub mod states;
 pub mod controls;
 pub mod settings;
 pub mod commands;
+pub mod profiles;
+pub mod drills;
+pub mod storage;
 
 use bevy::prelude::*;
-use controls::GameControlsPlugin;
+use std::path::PathBuf;
+use leafwing_input_manager::prelude::InputMap;
+use controls::{GameAction, GameControlsPlugin};
+use drills::DrillsPlugin;
+use profiles::PlayerProfilesPlugin;
+use settings::GameSettings;
 use states::GameStatesPlugin;
+use storage::DataDir;
 
-pub struct GamePlugin;
+#[derive(Clone, Default)]
+pub struct GamePlugin {
+    controls: GameControlsPlugin,
+    data_dir: DataDir,
+}
+
+impl GamePlugin {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_input_map(mut self, input_map: InputMap<GameAction>) -> Self {
+        self.controls = self.controls.with_input_map(input_map);
+        self
+    }
+
+    /// Reads and writes profiles and drills in `path` instead of the platform data directory.
+    pub fn with_data_dir(mut self, path: impl Into<PathBuf>) -> Self {
+        self.data_dir = DataDir::Path(path.into());
+        self
+    }
+
+    /// Keeps profiles and drills in memory instead of reading and writing files.
+    pub fn without_storage(mut self) -> Self {
+        self.data_dir = DataDir::InMemory;
+        self
+    }
+}
 
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
         app
-            .add_plugins((
-                GameStatesPlugin,
-                GameControlsPlugin,
-            ));
+            .init_resource::<GameSettings>()
+            .add_plugins((GameStatesPlugin, self.controls.clone()));
+
+        app.add_plugins((
+            PlayerProfilesPlugin::new(self.data_dir.clone()),
+            DrillsPlugin::new(self.data_dir.clone()),
+        ));
     }
 }
\ No newline at end of file
@@ -1,7 +1,12 @@
 use bevy::prelude::*;
-use super::settings::{GameSettings, PlayerType};
+use leafwing_input_manager::prelude::InputMap;
+use super::controls::{DefaultControls, GameAction};
+use super::drills::{Drill, DrillPresets, SelectedDrill};
+use super::profiles::{PlayerProfile, PlayerProfiles};
+use super::settings::{GameMode, GameSettings, PlayerType};
+use super::states::GameState;
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct UpdatePlayerCommand {
     player_num: usize,
     player_type: PlayerType,
@@ -22,4 +27,242 @@ impl Command for UpdatePlayerCommand {
             settings.update_players(self.player_num, self.player_type);
         }
     }
+}
+
+#[derive(Clone)]
+pub struct ResetControlsCommand;
+
+impl Command for ResetControlsCommand {
+    fn apply(self, world: &mut World) {
+        let input_map = world.resource::<DefaultControls>().0.clone();
+        world.insert_resource::<InputMap<GameAction>>(input_map);
+    }
+}
+
+#[derive(Clone)]
+pub struct UpdateProfileCommand {
+    player_num: usize,
+    profile: Option<String>,
+}
+
+impl UpdateProfileCommand {
+    pub fn new(player_num: usize, profile: Option<String>) -> Self {
+        Self {
+            player_num,
+            profile,
+        }
+    }
+}
+
+impl Command for UpdateProfileCommand {
+    fn apply(self, world: &mut World) {
+        let other_player = 3 - self.player_num;
+        let profile = self.profile.as_deref()
+            .and_then(|name| world.resource::<PlayerProfiles>().get(name))
+            .cloned();
+
+        let mut settings = world.resource_mut::<GameSettings>();
+        if self.profile.is_some() && settings.get_profile(other_player) == self.profile.as_deref() {
+            settings.update_profile(other_player, None);
+        }
+        settings.update_profile(self.player_num, self.profile);
+
+        if let Some(profile) = profile {
+            profile.apply_bindings(&mut world.resource_mut::<InputMap<GameAction>>(), self.player_num);
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct CreateProfileCommand {
+    profile: PlayerProfile,
+}
+
+impl CreateProfileCommand {
+    pub fn new(profile: PlayerProfile) -> Self {
+        Self { profile }
+    }
+}
+
+impl Command for CreateProfileCommand {
+    fn apply(self, world: &mut World) {
+        world.resource_mut::<PlayerProfiles>().add(self.profile);
+    }
+}
+
+#[derive(Clone)]
+pub struct DeleteProfileCommand {
+    name: String,
+}
+
+impl DeleteProfileCommand {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into() }
+    }
+}
+
+impl Command for DeleteProfileCommand {
+    fn apply(self, world: &mut World) {
+        let mut settings = world.resource_mut::<GameSettings>();
+        for player_num in [1, 2] {
+            if settings.get_profile(player_num) == Some(&self.name) {
+                settings.update_profile(player_num, None);
+            }
+        }
+
+        world.resource_mut::<PlayerProfiles>().remove(&self.name);
+    }
+}
+
+#[derive(Clone)]
+pub struct SwapSidesCommand;
+
+impl Command for SwapSidesCommand {
+    fn apply(self, world: &mut World) {
+        world.resource_mut::<GameSettings>().swap_players();
+
+        let mut input_map = world.resource_mut::<InputMap<GameAction>>();
+        let (player1, player2) = (GameAction::player_actions(1), GameAction::player_actions(2));
+
+        for (action1, action2) in [(player1.0, player2.0), (player1.1, player2.1)] {
+            let (keys1, keys2) = (bound_keys(&input_map, action1), bound_keys(&input_map, action2));
+
+            for (action, keys) in [(action1, keys2), (action2, keys1)] {
+                input_map.clear_action(&action);
+                for key in keys {
+                    input_map.insert(action, key);
+                }
+            }
+        }
+    }
+}
+
+/// Players can only bind keyboard keys, see `listen_for_keys`.
+fn bound_keys(input_map: &InputMap<GameAction>, action: GameAction) -> Vec<KeyCode> {
+    input_map.get_buttonlike(&action)
+        .into_iter()
+        .flatten()
+        .filter_map(|binding| Reflect::as_any(&**binding).downcast_ref::<KeyCode>().copied())
+        .collect()
+}
+
+#[derive(Clone)]
+pub struct ChangeDifficultyCommand {
+    harder: bool,
+}
+
+impl ChangeDifficultyCommand {
+    pub fn harder() -> Self {
+        Self { harder: true }
+    }
+
+    pub fn easier() -> Self {
+        Self { harder: false }
+    }
+}
+
+impl Command for ChangeDifficultyCommand {
+    fn apply(self, world: &mut World) {
+        let mut settings = world.resource_mut::<GameSettings>();
+
+        for player_num in [1, 2] {
+            let player_type = match player_num {
+                1 => *settings.get_player1(),
+                _ => *settings.get_player2(),
+            };
+
+            if let PlayerType::Computer(difficulty) = player_type {
+                let difficulty = if self.harder { difficulty.harder() } else { difficulty.easier() };
+                settings.update_players(player_num, PlayerType::Computer(difficulty));
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct StartGameCommand {
+    mode: GameMode,
+}
+
+impl StartGameCommand {
+    pub fn new(mode: GameMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl Command for StartGameCommand {
+    fn apply(self, world: &mut World) {
+        world.resource_mut::<GameSettings>().set_mode(self.mode);
+        world.resource_mut::<NextState<GameState>>().set(GameState::Playing);
+    }
+}
+
+#[derive(Clone)]
+pub struct SelectDrillCommand {
+    name: String,
+}
+
+impl SelectDrillCommand {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into() }
+    }
+}
+
+impl Command for SelectDrillCommand {
+    fn apply(self, world: &mut World) {
+        if let Some(drill) = world.resource::<DrillPresets>().get(&self.name).cloned() {
+            world.insert_resource(SelectedDrill(drill));
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct SaveDrillCommand {
+    drill: Drill,
+}
+
+impl SaveDrillCommand {
+    pub fn new(drill: Drill) -> Self {
+        Self { drill }
+    }
+}
+
+impl Command for SaveDrillCommand {
+    fn apply(self, world: &mut World) {
+        world.resource_mut::<DrillPresets>().save(self.drill);
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChangeGameStateCommand {
+    state: GameState,
+}
+
+impl ChangeGameStateCommand {
+    pub fn new(state: GameState) -> Self {
+        Self { state }
+    }
+}
+
+impl Command for ChangeGameStateCommand {
+    fn apply(self, world: &mut World) {
+        world.resource_mut::<NextState<GameState>>().set(self.state);
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SetTimeScaleCommand {
+    scale: f32,
+}
+
+impl SetTimeScaleCommand {
+    pub fn new(scale: f32) -> Self {
+        Self { scale: scale.max(0.) }
+    }
+}
+
+impl Command for SetTimeScaleCommand {
+    fn apply(self, world: &mut World) {
+        world.resource_mut::<Time<Virtual>>().set_relative_speed(self.scale);
+    }
 }
\ No newline at end of file
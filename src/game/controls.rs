@@ -11,7 +11,15 @@ pub enum GameAction {
 }
 
 impl GameAction {
-    fn default_input_map() -> InputMap<GameAction> {
+    pub fn player_actions(player_num: usize) -> (GameAction, GameAction) {
+        match player_num {
+            1 => (Self::Player1Up, Self::Player1Down),
+            2 => (Self::Player2Up, Self::Player2Down),
+            _ => panic!("Invalid player num {}", player_num),
+        }
+    }
+
+    pub fn default_input_map() -> InputMap<GameAction> {
         let mut input_map = InputMap::default();
         input_map.insert(Self::Player1Up, KeyCode::ArrowUp);
         input_map.insert(Self::Player1Down, KeyCode::ArrowDown);
@@ -23,6 +31,15 @@ impl GameAction {
     }
 }
 
+#[derive(Resource, Clone)]
+pub struct DefaultControls(pub InputMap<GameAction>);
+
+#[derive(Event, Debug)]
+pub struct ControlRemapped {
+    pub action: GameAction,
+    pub key: KeyCode,
+}
+
 #[derive(Resource, Default, Clone, Copy)]
 pub struct ControlRemapping {
     current_action: Option<GameAction>,
@@ -37,6 +54,10 @@ impl ControlRemapping {
         }
     }
 
+    pub fn is_listening(&self) -> bool {
+        self.is_listening
+    }
+
     pub fn stop_remapping(&mut self) {
         self.current_action = None;
         self.is_listening = false;
@@ -47,12 +68,14 @@ pub fn listen_for_keys(
     mut mapping: ResMut<ControlRemapping>,
     mut key_map: ResMut<InputMap<GameAction>>,
     keys: Res<ButtonInput<KeyCode>>,
+    mut remapped: EventWriter<ControlRemapped>,
 ) {
     if mapping.is_listening && mapping.current_action.is_some() {
         if let Some(control) = mapping.current_action {
             for key in keys.get_pressed() {
                 key_map.clear_action(&control);
                 key_map.insert(control, *key);
+                remapped.send(ControlRemapped { action: control, key: *key });
 
                 mapping.stop_remapping();
             }
@@ -60,15 +83,36 @@ pub fn listen_for_keys(
     }
 }
 
-pub struct GameControlsPlugin;
+#[derive(Clone)]
+pub struct GameControlsPlugin {
+    input_map: InputMap<GameAction>,
+}
+
+impl Default for GameControlsPlugin {
+    fn default() -> Self {
+        Self { input_map: GameAction::default_input_map() }
+    }
+}
+
+impl GameControlsPlugin {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_input_map(mut self, input_map: InputMap<GameAction>) -> Self {
+        self.input_map = input_map;
+        self
+    }
+}
 
 impl Plugin for GameControlsPlugin {
     fn build(&self, app: &mut App) {
         app
             .add_plugins(InputManagerPlugin::<GameAction>::default())
+            .add_event::<ControlRemapped>()
             .init_resource::<ControlRemapping>()
             .init_resource::<ActionState<GameAction>>()
-            .init_resource::<InputMap<GameAction>>()
-            .insert_resource(GameAction::default_input_map());
+            .insert_resource(self.input_map.clone())
+            .insert_resource(DefaultControls(self.input_map.clone()));
     }
 }
\ No newline at end of file
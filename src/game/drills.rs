@@ -0,0 +1,201 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use super::storage::{self, DataDir};
+
+#[derive(Serialize, Deserialize, Default, Clone, Copy, PartialEq, Debug)]
+pub enum TargetZone {
+    #[default]
+    Any,
+    Top,
+    Middle,
+    Bottom,
+}
+
+impl TargetZone {
+    pub const ALL: [TargetZone; 4] = [TargetZone::Any, TargetZone::Top, TargetZone::Middle, TargetZone::Bottom];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TargetZone::Any => "any",
+            TargetZone::Top => "top",
+            TargetZone::Middle => "middle",
+            TargetZone::Bottom => "bottom",
+        }
+    }
+
+    pub fn contains(&self, offset: f32, height: f32) -> bool {
+        let third = height / 6.0;
+        match self {
+            TargetZone::Any => true,
+            TargetZone::Top => offset > third,
+            TargetZone::Middle => offset.abs() <= third,
+            TargetZone::Bottom => offset < -third,
+        }
+    }
+}
+
+impl FromStr for TargetZone {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        TargetZone::ALL.into_iter()
+            .find(|zone| zone.name() == name)
+            .ok_or_else(|| format!("Unknown target zone {}", name))
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default, Debug)]
+#[serde(default)]
+pub struct DrillStats {
+    pub launched: u32,
+    pub returns: u32,
+    pub on_target: u32,
+}
+
+impl DrillStats {
+    pub fn add(&mut self, other: &DrillStats) {
+        self.launched += other.launched;
+        self.returns += other.returns;
+        self.on_target += other.on_target;
+    }
+
+    pub fn accuracy(&self) -> f32 {
+        if self.returns == 0 { 0. }
+        else { self.on_target as f32 / self.returns as f32 * 100. }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Drill {
+    pub name: String,
+    pub min_speed: f32,
+    pub max_speed: f32,
+    pub min_angle: f32,
+    pub max_angle: f32,
+    pub spin: f32,
+    pub interval: f32,
+    pub target: TargetZone,
+    #[serde(default)]
+    pub stats: DrillStats,
+}
+
+impl Drill {
+    fn new(name: &str, speed: (f32, f32), angle: (f32, f32), spin: f32, interval: f32, target: TargetZone) -> Self {
+        Self {
+            name: name.to_string(),
+            min_speed: speed.0,
+            max_speed: speed.1,
+            min_angle: angle.0,
+            max_angle: angle.1,
+            spin,
+            interval,
+            target,
+            stats: DrillStats::default(),
+        }
+    }
+}
+
+impl Default for Drill {
+    fn default() -> Self {
+        Self::new("Basics", (300., 400.), (-15., 15.), 0., 1.5, TargetZone::Any)
+    }
+}
+
+#[derive(Resource, Serialize, Deserialize)]
+pub struct DrillPresets {
+    drills: Vec<Drill>,
+}
+
+impl Default for DrillPresets {
+    fn default() -> Self {
+        Self {
+            drills: vec![
+                Drill::default(),
+                Drill::new("Fast balls", (600., 800.), (-20., 20.), 0., 1.5, TargetZone::Any),
+                Drill::new("Wide angles", (300., 500.), (-60., 60.), 0., 2., TargetZone::Any),
+                Drill::new("Curveballs", (300., 450.), (-20., 20.), 30., 2., TargetZone::Any),
+                Drill::new("Aim high", (300., 400.), (-15., 15.), 0., 2., TargetZone::Top),
+                Drill::new("Aim low", (300., 400.), (-15., 15.), 0., 2., TargetZone::Bottom),
+            ],
+        }
+    }
+}
+
+impl DrillPresets {
+    pub fn iter(&self) -> impl Iterator<Item = &Drill> {
+        self.drills.iter()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Drill> {
+        self.drills.iter().find(|drill| drill.name == name)
+    }
+
+    pub fn save(&mut self, drill: Drill) {
+        if drill.name.trim().is_empty() {
+            return;
+        }
+
+        match self.drills.iter_mut().find(|preset| preset.name == drill.name) {
+            Some(preset) => *preset = Drill { stats: preset.stats, ..drill },
+            None => self.drills.push(drill),
+        }
+    }
+
+    pub fn record(&mut self, name: &str, stats: &DrillStats) {
+        if let Some(drill) = self.drills.iter_mut().find(|drill| drill.name == name) {
+            drill.stats.add(stats);
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct SelectedDrill(pub Drill);
+
+#[derive(Resource)]
+pub struct DrillStorage {
+    path: Option<PathBuf>,
+}
+
+impl DrillStorage {
+    pub fn new(data_dir: &DataDir) -> Self {
+        Self { path: data_dir.file("drills.ron") }
+    }
+
+    pub fn load(&self) -> DrillPresets {
+        storage::load(self.path.as_deref())
+    }
+
+    pub fn save(&self, presets: &DrillPresets) {
+        storage::save(self.path.as_deref(), presets);
+    }
+}
+
+fn save_drills(storage: Res<DrillStorage>, presets: Res<DrillPresets>) {
+    storage.save(&presets);
+}
+
+#[derive(Default)]
+pub struct DrillsPlugin {
+    data_dir: DataDir,
+}
+
+impl DrillsPlugin {
+    pub fn new(data_dir: DataDir) -> Self {
+        Self { data_dir }
+    }
+}
+
+impl Plugin for DrillsPlugin {
+    fn build(&self, app: &mut App) {
+        let storage = DrillStorage::new(&self.data_dir);
+
+        app.insert_resource(storage.load())
+            .insert_resource(storage)
+            .init_resource::<SelectedDrill>()
+            .add_systems(Update, save_drills
+                .run_if(resource_changed::<DrillPresets>.and(not(resource_added::<DrillPresets>))));
+    }
+}
\ No newline at end of file
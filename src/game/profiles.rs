@@ -0,0 +1,160 @@
+use bevy::prelude::*;
+use leafwing_input_manager::prelude::InputMap;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use super::controls::{ControlRemapped, GameAction};
+use super::settings::GameSettings;
+use super::storage::{self, DataDir};
+
+pub const PROFILE_COLORS: [(&str, Color); 6] = [
+    ("white", Color::WHITE),
+    ("red", Color::srgb(0.9, 0.2, 0.2)),
+    ("orange", Color::srgb(1.0, 0.6, 0.1)),
+    ("yellow", Color::srgb(1.0, 0.9, 0.2)),
+    ("green", Color::srgb(0.2, 0.8, 0.3)),
+    ("blue", Color::srgb(0.3, 0.5, 1.0)),
+];
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default, Debug)]
+#[serde(default)]
+pub struct ProfileStats {
+    pub matches_played: u32,
+    pub matches_won: u32,
+    pub points_scored: u32,
+    pub points_conceded: u32,
+    pub practice_best_returns: u32,
+    pub practice_best_time: f32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PlayerProfile {
+    pub name: String,
+    pub color: Color,
+    pub up: Option<KeyCode>,
+    pub down: Option<KeyCode>,
+    pub stats: ProfileStats,
+}
+
+impl PlayerProfile {
+    pub fn new(name: impl Into<String>, color: Color) -> Self {
+        Self {
+            name: name.into(),
+            color,
+            up: None,
+            down: None,
+            stats: ProfileStats::default(),
+        }
+    }
+
+    pub fn apply_bindings(&self, input_map: &mut InputMap<GameAction>, player_num: usize) {
+        let (up, down) = GameAction::player_actions(player_num);
+
+        for (action, key) in [(up, self.up), (down, self.down)] {
+            if let Some(key) = key {
+                input_map.clear_action(&action);
+                input_map.insert(action, key);
+            }
+        }
+    }
+}
+
+#[derive(Resource, Serialize, Deserialize, Default)]
+pub struct PlayerProfiles {
+    profiles: Vec<PlayerProfile>,
+}
+
+impl PlayerProfiles {
+    pub fn iter(&self) -> impl Iterator<Item = &PlayerProfile> {
+        self.profiles.iter()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&PlayerProfile> {
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut PlayerProfile> {
+        self.profiles.iter_mut().find(|profile| profile.name == name)
+    }
+
+    pub fn add(&mut self, profile: PlayerProfile) -> bool {
+        if profile.name.trim().is_empty() || self.get(&profile.name).is_some() {
+            return false;
+        }
+
+        self.profiles.push(profile);
+        true
+    }
+
+    pub fn remove(&mut self, name: &str) {
+        self.profiles.retain(|profile| profile.name != name);
+    }
+}
+
+#[derive(Resource)]
+pub struct ProfileStorage {
+    path: Option<PathBuf>,
+}
+
+impl ProfileStorage {
+    pub fn new(data_dir: &DataDir) -> Self {
+        Self { path: data_dir.file("profiles.ron") }
+    }
+
+    pub fn load(&self) -> PlayerProfiles {
+        storage::load(self.path.as_deref())
+    }
+
+    pub fn save(&self, profiles: &PlayerProfiles) {
+        storage::save(self.path.as_deref(), profiles);
+    }
+}
+
+fn save_profiles(storage: Res<ProfileStorage>, profiles: Res<PlayerProfiles>) {
+    storage.save(&profiles);
+}
+
+fn store_profile_bindings(
+    mut remapped: EventReader<ControlRemapped>,
+    settings: Res<GameSettings>,
+    mut profiles: ResMut<PlayerProfiles>,
+) {
+    for ControlRemapped { action, key } in remapped.read() {
+        for player_num in [1, 2] {
+            let (up, down) = GameAction::player_actions(player_num);
+            let Some(profile) = settings.get_profile(player_num).and_then(|name| profiles.get_mut(name)) else {
+                continue;
+            };
+
+            if *action == up {
+                profile.up = Some(*key);
+            } else if *action == down {
+                profile.down = Some(*key);
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct PlayerProfilesPlugin {
+    data_dir: DataDir,
+}
+
+impl PlayerProfilesPlugin {
+    pub fn new(data_dir: DataDir) -> Self {
+        Self { data_dir }
+    }
+}
+
+impl Plugin for PlayerProfilesPlugin {
+    fn build(&self, app: &mut App) {
+        let storage = ProfileStorage::new(&self.data_dir);
+
+        app.insert_resource(storage.load())
+            .insert_resource(storage)
+            .add_systems(Update, (
+                store_profile_bindings.run_if(on_event::<ControlRemapped>),
+                save_profiles.run_if(resource_changed::<PlayerProfiles>.and(not(resource_added::<PlayerProfiles>))),
+            ).chain());
+    }
+}
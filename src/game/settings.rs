@@ -1,12 +1,27 @@
 use bevy::prelude::*;
+use std::fmt;
+use std::str::FromStr;
 
 #[derive(Resource)]
 pub struct GameSettings {
+    mode: GameMode,
     player1: PlayerType,
     player2: PlayerType,
+    profile1: Option<String>,
+    profile2: Option<String>,
+    auto_pause: bool,
+    series_length: u32,
 }
 
 impl GameSettings {
+    pub fn get_mode(&self) -> GameMode {
+        self.mode
+    }
+
+    pub fn set_mode(&mut self, mode: GameMode) {
+        self.mode = mode;
+    }
+
     pub fn get_player1(&self) -> &PlayerType {
         &self.player1
     }
@@ -22,24 +37,107 @@ impl GameSettings {
             _ => panic!("Invalid player num {}", player_num),
         }
     }
+
+    pub fn swap_players(&mut self) {
+        std::mem::swap(&mut self.player1, &mut self.player2);
+        std::mem::swap(&mut self.profile1, &mut self.profile2);
+    }
+
+    pub fn get_profile(&self, player_num: usize) -> Option<&str> {
+        match player_num {
+            1 => self.profile1.as_deref(),
+            2 => self.profile2.as_deref(),
+            _ => panic!("Invalid player num {}", player_num),
+        }
+    }
+
+    pub fn update_profile(&mut self, player_num: usize, profile: Option<String>) {
+        match player_num {
+            1 => self.profile1 = profile,
+            2 => self.profile2 = profile,
+            _ => panic!("Invalid player num {}", player_num),
+        }
+    }
+
+    pub fn is_auto_pause(&self) -> bool {
+        self.auto_pause
+    }
+
+    pub fn set_auto_pause(&mut self, auto_pause: bool) {
+        self.auto_pause = auto_pause;
+    }
+
+    pub fn get_series_length(&self) -> u32 {
+        self.series_length
+    }
+
+    pub fn set_series_length(&mut self, series_length: u32) {
+        self.series_length = series_length;
+    }
 }
 
 impl Default for GameSettings {
     fn default() -> Self {
         Self {
+            mode: GameMode::Versus,
             player1: PlayerType::Human,
             player2: PlayerType::Computer(Difficulty::Easy),
+            profile1: None,
+            profile2: None,
+            auto_pause: true,
+            series_length: 1,
+        }
+    }
+}
+
+#[derive(Default, PartialEq, Eq, Copy, Clone, Debug)]
+pub enum GameMode {
+    #[default]
+    Versus,
+    Practice,
+    Training,
+}
+
+impl FromStr for GameMode {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "versus" => Ok(GameMode::Versus),
+            "practice" => Ok(GameMode::Practice),
+            "training" => Ok(GameMode::Training),
+            _ => Err(format!("Unknown game mode {}", name)),
         }
     }
 }
 
-#[derive(Component, PartialEq, Copy, Clone)]
+#[derive(Component, PartialEq, Copy, Clone, Debug)]
 pub enum PlayerType {
     Human,
     Computer(Difficulty),
 }
 
-#[derive(Default, PartialEq, Copy, Clone)]
+impl FromStr for PlayerType {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "human" => Ok(PlayerType::Human),
+            difficulty => difficulty.strip_prefix("ai:").unwrap_or(difficulty).parse().map(PlayerType::Computer),
+        }
+    }
+}
+
+impl fmt::Display for PlayerType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PlayerType::Human => write!(f, "human"),
+            PlayerType::Computer(difficulty) => write!(f, "ai:{}", difficulty),
+        }
+    }
+}
+
+#[derive(Default, PartialEq, Copy, Clone, Debug)]
 pub enum Difficulty {
     #[default]
     Easy,
@@ -48,6 +146,8 @@ pub enum Difficulty {
 }
 
 impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Difficult, Difficulty::Impossible];
+
     pub fn speed(&self) -> f32 {
         match self {
             Difficulty::Easy => 2.,
@@ -55,4 +155,52 @@ impl Difficulty {
             Difficulty::Impossible => 6.,
         }
     }
+
+    /// How far from the paddle centre the computer tries to meet the ball, as a
+    /// fraction of half the paddle height. Off-centre hits return at an angle.
+    pub fn aim(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.,
+            Difficulty::Difficult => 0.5,
+            Difficulty::Impossible => 0.9,
+        }
+    }
+
+    pub fn harder(&self) -> Self {
+        match self {
+            Difficulty::Easy => Difficulty::Difficult,
+            Difficulty::Difficult | Difficulty::Impossible => Difficulty::Impossible,
+        }
+    }
+
+    pub fn easier(&self) -> Self {
+        match self {
+            Difficulty::Easy | Difficulty::Difficult => Difficulty::Easy,
+            Difficulty::Impossible => Difficulty::Difficult,
+        }
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "easy" => Ok(Difficulty::Easy),
+            "difficult" => Ok(Difficulty::Difficult),
+            "impossible" => Ok(Difficulty::Impossible),
+            _ => Err(format!("Unknown difficulty {}", name)),
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Difficult => "difficult",
+            Difficulty::Impossible => "impossible",
+        };
+        write!(f, "{}", name)
+    }
 }
\ No newline at end of file
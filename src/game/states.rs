@@ -1,15 +1,33 @@
+use bevy::ecs::schedule::ScheduleLabel;
 use bevy::prelude::*;
 
 #[derive(States, Default, Debug, Clone, PartialEq, Eq, Hash)]
 pub enum GameState {
     #[default]
     Main,
-    Controls,
-    Startgame,
     Playing,
     Endgame,
 }
 
+#[derive(SubStates, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[source(GameState = GameState::Playing)]
+pub enum MatchState {
+    #[default]
+    Serving,
+    Rally,
+    PointScored,
+}
+
+/// Whether the next point can decide the match. Kept apart from [`MatchState`]
+/// so it holds for the serve and the rally that follows.
+#[derive(SubStates, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[source(GameState = GameState::Playing)]
+pub enum MatchPointState {
+    #[default]
+    Inactive,
+    Active,
+}
+
 #[derive(States, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum PausedState {
     #[default]
@@ -17,6 +35,27 @@ pub enum PausedState {
     Paused,
 }
 
+#[derive(States, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MenuScreen {
+    #[default]
+    None,
+    Main,
+    Controls,
+    StartGame,
+    Paused,
+    Settings,
+    Rules,
+    Profiles,
+    Training,
+    Endgame,
+}
+
+impl MenuScreen {
+    pub fn is_root(&self) -> bool {
+        matches!(self, MenuScreen::Main | MenuScreen::Endgame)
+    }
+}
+
 #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct MainSet;
 #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
@@ -26,8 +65,22 @@ pub struct StartGameSet;
 #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct PlayingSet;
 #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ServingSet;
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RallySet;
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MatchPointSet;
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct PausedSet;
 #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SettingsSet;
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RulesSet;
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ProfilesSet;
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TrainingSet;
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct EndgameSet;
 
 pub struct GameStatesPlugin;
@@ -36,23 +89,35 @@ impl Plugin for GameStatesPlugin {
     fn build(&self, app: &mut App) {
         app
             .init_state::<GameState>()
+            .add_sub_state::<MatchState>()
+            .add_sub_state::<MatchPointState>()
             .init_state::<PausedState>()
+            .init_state::<MenuScreen>()
             .configure_sets(Update, (
-                MainSet.run_if(in_state(GameState::Main)),
-                ControlsSet.run_if(in_state(GameState::Controls)),
-                StartGameSet.run_if(in_state(GameState::Startgame)),
-                PlayingSet
-                    .run_if(in_state(GameState::Playing))
-                    .run_if(in_state(PausedState::Playing)),
-                PausedSet
-                    .run_if(in_state(GameState::Playing))
-                    .run_if(in_state(PausedState::Paused)),
-                EndgameSet.run_if(in_state(GameState::Endgame)),
-            ))
-            .configure_sets(FixedUpdate,
-                PlayingSet
-                    .run_if(in_state(GameState::Playing))
-                    .run_if(in_state(PausedState::Playing)),
-            );
+                MainSet.run_if(in_state(MenuScreen::Main)),
+                ControlsSet.run_if(in_state(MenuScreen::Controls)),
+                StartGameSet.run_if(in_state(MenuScreen::StartGame)),
+                PausedSet.run_if(in_state(MenuScreen::Paused)),
+                SettingsSet.run_if(in_state(MenuScreen::Settings)),
+                RulesSet.run_if(in_state(MenuScreen::Rules)),
+                ProfilesSet.run_if(in_state(MenuScreen::Profiles)),
+                TrainingSet.run_if(in_state(MenuScreen::Training)),
+                EndgameSet.run_if(in_state(MenuScreen::Endgame)),
+            ));
+
+        configure_match_sets(app, Update);
+        configure_match_sets(app, FixedUpdate);
+        configure_match_sets(app, FixedPostUpdate);
     }
+}
+
+fn configure_match_sets(app: &mut App, schedule: impl ScheduleLabel) {
+    app.configure_sets(schedule, (
+        PlayingSet
+            .run_if(in_state(GameState::Playing))
+            .run_if(in_state(PausedState::Playing)),
+        ServingSet.in_set(PlayingSet).run_if(in_state(MatchState::Serving)),
+        RallySet.in_set(PlayingSet).run_if(in_state(MatchState::Rally)),
+        MatchPointSet.in_set(PlayingSet).run_if(in_state(MatchPointState::Active)),
+    ));
 }
\ No newline at end of file
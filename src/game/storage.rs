@@ -0,0 +1,78 @@
+use bevy::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Where profiles and drills are kept between runs.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum DataDir {
+    /// `PONG_DATA_DIR` when set, otherwise the platform's per-user data directory.
+    #[default]
+    Platform,
+    Path(PathBuf),
+    /// Never touches the file system.
+    InMemory,
+}
+
+impl DataDir {
+    pub fn file(&self, name: &str) -> Option<PathBuf> {
+        match self {
+            Self::Platform => platform_dir().map(|dir| dir.join(name)),
+            Self::Path(dir) => Some(dir.join(name)),
+            Self::InMemory => None,
+        }
+    }
+}
+
+fn platform_dir() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os("PONG_DATA_DIR") {
+        return Some(dir.into());
+    }
+
+    let base = if cfg!(windows) {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        env::var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+    };
+
+    base.map(|dir| dir.join("pong"))
+}
+
+pub(crate) fn load<T: DeserializeOwned + Default>(path: Option<&Path>) -> T {
+    let Some(path) = path else {
+        return T::default();
+    };
+    let Ok(contents) = fs::read_to_string(path) else {
+        return T::default();
+    };
+
+    ron::de::from_str(&contents).unwrap_or_else(|error| {
+        warn!("Could not parse {}: {}", path.display(), error);
+        T::default()
+    })
+}
+
+pub(crate) fn save<T: Serialize>(path: Option<&Path>, value: &T) {
+    let Some(path) = path else {
+        return;
+    };
+
+    let result = ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())
+        .map_err(|error| error.to_string())
+        .and_then(|contents| {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir).map_err(|error| error.to_string())?;
+            }
+            fs::write(path, contents).map_err(|error| error.to_string())
+        });
+
+    if let Err(error) = result {
+        warn!("Could not save {}: {}", path.display(), error);
+    }
+}
\ No newline at end of file
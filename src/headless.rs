@@ -0,0 +1,50 @@
+use bevy::app::ScheduleRunnerPlugin;
+use bevy::asset::AssetPlugin;
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+
+/// Runs the game without a window or renderer.
+///
+/// By default every update advances time by exactly one fixed timestep, so the
+/// simulation runs as fast as the CPU allows and is independent of wall-clock time.
+#[derive(Default)]
+pub struct HeadlessPlugin {
+    real_time: bool,
+}
+
+impl HeadlessPlugin {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Updates once per fixed timestep of wall-clock time, for interactive frontends.
+    pub fn in_real_time(mut self) -> Self {
+        self.real_time = true;
+        self
+    }
+}
+
+impl Plugin for HeadlessPlugin {
+    fn build(&self, app: &mut App) {
+        let timestep = Time::<Fixed>::default().timestep();
+
+        if self.real_time {
+            app.add_plugins(MinimalPlugins.set(ScheduleRunnerPlugin::run_loop(timestep)));
+        } else {
+            app.add_plugins(MinimalPlugins)
+                .insert_resource(TimeUpdateStrategy::ManualDuration(timestep));
+        }
+
+        app.add_plugins((
+            StatesPlugin,
+            InputPlugin,
+            AssetPlugin::default(),
+            TransformPlugin,
+            HierarchyPlugin,
+        ))
+            .init_asset::<Mesh>()
+            .init_asset::<ColorMaterial>();
+    }
+}
\ No newline at end of file
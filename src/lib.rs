@@ -0,0 +1,67 @@
+pub mod pong;
+pub mod game;
+pub mod ui;
+pub mod locale;
+pub mod debug;
+pub mod console;
+pub mod headless;
+pub mod cli;
+pub mod simulation;
+pub mod terminal;
+pub mod spectator;
+
+use bevy::app::PluginGroupBuilder;
+use bevy::prelude::*;
+use bevy_egui::EguiPlugin;
+
+pub use console::ConsolePlugin;
+pub use debug::DebugOverlayPlugin;
+pub use game::GamePlugin;
+pub use headless::HeadlessPlugin;
+pub use pong::PongPlugin;
+pub use ui::MenuSystemsPlugin;
+
+/// Individual plugins can be configured with `PluginGroupBuilder::set`,
+/// e.g. `PongGamePlugins::default().build().set(PongPlugin::new().without_physics())`.
+#[derive(Default)]
+pub struct PongGamePlugins {
+    without_egui: bool,
+}
+
+impl PongGamePlugins {
+    /// Builds every plugin in the group `without_egui`.
+    pub fn without_egui(mut self) -> Self {
+        self.without_egui = true;
+        self
+    }
+}
+
+impl PluginGroup for PongGamePlugins {
+    fn build(self) -> PluginGroupBuilder {
+        let (mut menus, mut debug, mut console) = (
+            MenuSystemsPlugin::new(),
+            DebugOverlayPlugin::new(),
+            ConsolePlugin::new(),
+        );
+        if self.without_egui {
+            menus = menus.without_egui();
+            debug = debug.without_egui();
+            console = console.without_egui();
+        }
+
+        PluginGroupBuilder::start::<Self>()
+            .add(GamePlugin::new())
+            .add(PongPlugin::new())
+            .add(menus)
+            .add(debug)
+            .add(console)
+    }
+}
+
+/// The egui plugins add `EguiPlugin` themselves unless the app already has it.
+/// Building one `without_egui` leaves it to the host app instead.
+pub(crate) fn add_egui_if_missing(app: &mut App, install_egui: bool) {
+    if install_egui && !app.is_plugin_added::<EguiPlugin>() {
+        app.add_plugins(EguiPlugin);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,171 @@
+use bevy::prelude::*;
+use bevy::asset::{io::Reader, AssetLoader, LoadContext};
+use bevy::utils::HashMap;
+use serde::Deserialize;
+use std::fmt;
+
+pub const DEFAULT_LANGUAGE: &str = "en";
+pub const LANGUAGES: [(&str, &str); 2] = [
+    ("en", "English"),
+    ("nl", "Nederlands"),
+];
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum Message {
+    Text(String),
+    Plural {
+        one: String,
+        other: String,
+    },
+}
+
+#[derive(Asset, TypePath, Deserialize, Debug)]
+pub struct Locale {
+    pub messages: HashMap<String, Message>,
+}
+
+#[derive(Default)]
+pub struct LocaleLoader;
+
+#[derive(Debug)]
+pub enum LocaleLoaderError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+}
+
+impl fmt::Display for LocaleLoaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Could not read locale: {}", error),
+            Self::Ron(error) => write!(f, "Could not parse locale: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for LocaleLoaderError {}
+
+impl AssetLoader for LocaleLoader {
+    type Asset = Locale;
+    type Settings = ();
+    type Error = LocaleLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await.map_err(LocaleLoaderError::Io)?;
+
+        ron::de::from_bytes(&bytes).map_err(LocaleLoaderError::Ron)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["locale.ron"]
+    }
+}
+
+#[derive(Resource)]
+pub struct Localization {
+    language: String,
+    locales: HashMap<String, Handle<Locale>>,
+}
+
+impl Localization {
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    pub fn set_language(&mut self, language: impl Into<String>) {
+        let language = language.into();
+        if self.locales.contains_key(&language) {
+            self.language = language;
+        } else {
+            warn!("Unknown language {:?}", language);
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct Translations {
+    messages: HashMap<String, Message>,
+}
+
+impl Translations {
+    pub fn text(&self, key: &str) -> String {
+        self.format(key, &[])
+    }
+
+    pub fn format(&self, key: &str, args: &[(&str, &str)]) -> String {
+        match self.messages.get(key) {
+            Some(Message::Text(text)) => substitute(text, args),
+            Some(Message::Plural { other, .. }) => substitute(other, args),
+            None => key.to_string(),
+        }
+    }
+
+    pub fn plural(&self, key: &str, count: u32, args: &[(&str, &str)]) -> String {
+        let count_text = count.to_string();
+        let mut args = args.to_vec();
+        args.push(("count", &count_text));
+
+        match self.messages.get(key) {
+            Some(Message::Plural { one, .. }) if count == 1 => substitute(one, &args),
+            Some(Message::Plural { other, .. }) => substitute(other, &args),
+            Some(Message::Text(text)) => substitute(text, &args),
+            None => key.to_string(),
+        }
+    }
+}
+
+fn substitute(text: &str, args: &[(&str, &str)]) -> String {
+    args.iter().fold(text.to_string(), |text, (name, value)| {
+        text.replace(&format!("{{{}}}", name), value)
+    })
+}
+
+fn load_locales(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let locales = LANGUAGES.iter()
+        .map(|(code, _)| (code.to_string(), asset_server.load(format!("locales/{}.locale.ron", code))))
+        .collect();
+
+    commands.insert_resource(Localization {
+        language: DEFAULT_LANGUAGE.to_string(),
+        locales,
+    });
+}
+
+fn update_translations(
+    localization: Res<Localization>,
+    locales: Res<Assets<Locale>>,
+    mut translations: ResMut<Translations>,
+) {
+    let mut messages = HashMap::new();
+
+    for language in [DEFAULT_LANGUAGE, localization.language()] {
+        let locale = localization.locales.get(language)
+            .and_then(|handle| locales.get(handle));
+
+        if let Some(locale) = locale {
+            messages.extend(locale.messages.clone());
+        }
+    }
+
+    translations.messages = messages;
+}
+
+pub struct LocalizationPlugin;
+
+impl Plugin for LocalizationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<Locale>()
+            .init_asset_loader::<LocaleLoader>()
+            .init_resource::<Translations>()
+            .add_systems(Startup, load_locales)
+            .add_systems(Update, update_translations.run_if(
+                resource_exists_and_changed::<Localization>.or(on_event::<AssetEvent<Locale>>)
+            ));
+    }
+}
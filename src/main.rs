@@ -1,26 +1,76 @@
-mod pong;
-mod game;
-mod ui;
-
 use bevy::prelude::*;
 use bevy::window::PresentMode;
 
-use game::GamePlugin;
-use pong::PongPlugin;
-use ui::MenuSystemsPlugin;
+use pong::cli::{LaunchOptions, LaunchPlugin, ReportFormat, USAGE};
+use pong::pong::Rules;
+use pong::simulation::{self, BatchOptions};
+use pong::spectator::SpectatorPlugin;
+use pong::terminal::{self as text_mode, TerminalPlugin};
+use pong::{GamePlugin, HeadlessPlugin, PongGamePlugins, PongPlugin};
 
 fn create_camera(mut commands: Commands) {
-    commands.spawn(Camera2d::default());
+    commands.spawn(Camera2d);
 }
 
 fn main() {
-    App::new()
-        .add_plugins(
+    let options = match LaunchOptions::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("{}\n\n{}", error, USAGE);
+            std::process::exit(2);
+        },
+    };
+    if options.help {
+        println!("{}", USAGE);
+        return;
+    }
+
+    let arena = options.arena.unwrap_or_default().size();
+
+    if let Some(matches) = options.calibrate {
+        let reports = simulation::calibrate(BatchOptions {
+            matches,
+            target_score: options.target_score.unwrap_or(Rules::default().target_score),
+            arena_size: arena,
+            seed: options.seed.unwrap_or_default(),
+        });
+
+        match options.format.unwrap_or_default() {
+            ReportFormat::Json => println!("{}", simulation::to_json(&reports)),
+            ReportFormat::Csv => println!("{}", simulation::to_csv(&reports)),
+        }
+        return;
+    }
+
+    let mut app = App::new();
+
+    if options.headless {
+        app.add_plugins((
+            HeadlessPlugin::new(),
+            GamePlugin::new().without_storage(),
+            PongPlugin::new().with_arena_size(arena.x, arena.y),
+        ));
+    } else if options.terminal {
+        // Scripted runs should not touch the player's saved profiles and drills
+        let (headless, game) = if text_mode::is_interactive() {
+            (HeadlessPlugin::new().in_real_time(), GamePlugin::new())
+        } else {
+            (HeadlessPlugin::new(), GamePlugin::new().without_storage())
+        };
+        app.add_plugins((
+            headless,
+            game,
+            PongPlugin::new().with_arena_size(arena.x, arena.y),
+            TerminalPlugin,
+        ));
+    } else {
+        app.add_plugins(
             DefaultPlugins.set(
                 WindowPlugin {
                     primary_window: Some(
                         Window {
                             present_mode: PresentMode::AutoNoVsync,
+                            resolution: (arena.x, arena.y).into(),
                             ..default()
                         }
                     ),
@@ -28,11 +78,16 @@ fn main() {
                 }
             )
         )
-        .add_plugins((
-            GamePlugin,
-            MenuSystemsPlugin,
-            PongPlugin,
-        ))
-        .add_systems(Startup, create_camera)
+            .add_plugins(PongGamePlugins::default())
+            .add_systems(Startup, create_camera);
+    }
+
+    if let Some(port) = options.spectator {
+        app.add_plugins(SpectatorPlugin::new()
+            .with_address(([127, 0, 0, 1], port).into())
+            .with_decimation(options.spectator_every.unwrap_or(1)));
+    }
+
+    app.add_plugins(LaunchPlugin::new(options))
         .run();
 }
\ No newline at end of file
@@ -1,42 +1,173 @@
 mod components;
+pub mod commands;
 mod systems;
 mod resources;
 mod constants;
 mod observers;
+pub mod events;
 
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 
-use crate::game::states::{GameState, PausedState, PlayingSet};
+use crate::game::settings::GameSettings;
+use crate::locale::LocalizationPlugin;
+use crate::game::states::{
+    GameState, MatchState, PausedState, PlayingSet,
+    ServingSet, RallySet,
+};
+use events::*;
 use observers::*;
 use systems::*;
 
-pub struct PongPlugin;
+/// Arena size defaults to the primary window size when not set.
+#[derive(Clone)]
+pub struct PongPlugin {
+    arena_size: Option<Vec2>,
+    rules: Rules,
+    seed: Option<u64>,
+    install_physics: bool,
+}
+
+impl Default for PongPlugin {
+    fn default() -> Self {
+        Self {
+            arena_size: None,
+            rules: Rules::default(),
+            seed: None,
+            install_physics: true,
+        }
+    }
+}
+
+impl PongPlugin {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_arena_size(mut self, width: f32, height: f32) -> Self {
+        self.arena_size = Some(Vec2::new(width, height));
+        self
+    }
+
+    pub fn with_rules(mut self, rules: Rules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Leaves `RapierPhysicsPlugin` to the host app.
+    /// The game expects it in `FixedPostUpdate` with a fixed `TimestepMode`.
+    pub fn without_physics(mut self) -> Self {
+        self.install_physics = false;
+        self
+    }
+}
 
 impl Plugin for PongPlugin {
     fn build(&self, app: &mut App) {
+        // Step physics once per fixed tick so matches don't depend on the frame rate
+        if self.install_physics {
+            let dt = Time::<Fixed>::default().timestep().as_secs_f32();
+            app.insert_resource(TimestepMode::Fixed { dt, substeps: 1 })
+                .add_plugins(RapierPhysicsPlugin::<NoUserData>::default().in_schedule(FixedPostUpdate));
+        }
+
+        // The score, countdown and practice texts are localized
+        if !app.is_plugin_added::<LocalizationPlugin>() {
+            app.add_plugins(LocalizationPlugin);
+        }
+
         app.init_resource::<Score>()
-            .configure_sets(FixedUpdate, (
-                PhysicsSet::StepSimulation
-                    .run_if(in_state(GameState::Playing))
-                    .run_if(in_state(PausedState::Playing)),
-            ))
+            .insert_resource(self.rules)
+            .insert_resource(Arena { size: self.arena_size })
+            .insert_resource(self.seed.map(GameRng::new).unwrap_or_default())
+            .init_resource::<StateHash>()
+            .init_resource::<Series>()
+            .configure_sets(FixedPostUpdate, PhysicsSet::StepSimulation.run_if(in_state(PausedState::Playing)))
 
             .add_event::<OnPointScored>()
-            .add_plugins(RapierPhysicsPlugin::<NoUserData>::default())
+            .add_event::<BallHitPaddle>()
+            .add_event::<BallHitWall>()
+            .add_event::<BallEnteredGoal>()
+            .add_event::<MatchStarted>()
+            .add_event::<ServeStarted>()
+            .add_event::<PaddleHit>()
+            .add_event::<WallBounce>()
+            .add_event::<PointScored>()
+            .add_event::<MatchPointReached>()
+            .add_event::<MatchEnded>()
+            .init_resource::<MatchStats>()
+            .init_resource::<PublishedMatchPoints>()
             .add_observer(score_point)
-            .add_observer(reset_ball)
-            .add_observer(end_game)
-            .add_systems(OnEnter(GameState::Playing), setup_game)
-            .add_systems(OnExit(GameState::Playing), cleanup_game)
-            .add_systems(FixedUpdate, move_players.in_set(PlayingSet))
-            .add_systems(Update, (
-                speed_up_ball,
-                ball_paddle_collision,
-                detect_point,
-                update_score_display,
-            ).in_set(PlayingSet));
+            .add_systems(OnEnter(GameState::Playing), (setup_game, publish_match_started).chain())
+            .add_systems(OnExit(GameState::Playing), (
+                record_training_stats.run_if(resource_exists::<Training>),
+                cleanup_game,
+            ).chain())
+            .add_systems(OnEnter(MatchState::Serving), (
+                start_countdown,
+                check_match_point,
+                publish_match_point_reached,
+            ))
+            .add_systems(OnEnter(MatchState::Rally), publish_serve_started)
+            .add_systems(OnEnter(MatchState::PointScored), (
+                reset_ball,
+                next_serve,
+                store_practice_best.run_if(resource_exists::<Practice>),
+                end_stats_rally,
+            ))
+            .add_systems(OnExit(PausedState::Paused), start_countdown.run_if(in_state(MatchState::Rally)))
+            .add_systems(OnEnter(GameState::Endgame), (record_profile_stats, record_series_result, publish_match_ended))
+            .add_systems(OnEnter(GameState::Main), reset_series)
+            // Everything that feeds into the physics step runs on the fixed clock
+            .add_systems(FixedUpdate, (
+                update_countdown.run_if(resource_exists::<Countdown>),
+                move_players,
+            ).in_set(PlayingSet))
+            .add_systems(FixedUpdate, (
+                launch_training_ball.run_if(not(countdown_running)),
+                curve_training_ball,
+            ).after(update_countdown).run_if(resource_exists::<Training>).in_set(RallySet))
+            // Only the ball waits for the serve, the paddles stay under control
+            .add_systems(FixedPostUpdate, (
+                hold_ball.run_if(not(in_state(MatchState::Rally).and(not(countdown_running)))),
+                release_ball.run_if(in_state(MatchState::Rally).and(not(countdown_running))),
+            ).before(PhysicsSet::SyncBackend).in_set(PlayingSet))
+            .configure_sets(FixedPostUpdate, CollisionSet.in_set(RallySet).after(PhysicsSet::Writeback))
+            .add_systems(FixedPostUpdate, classify_collisions.in_set(CollisionSet))
+            .add_systems(FixedPostUpdate, (
+                (ball_paddle_collision, speed_up_ball).chain(),
+                detect_point.run_if(not(resource_exists::<Training>)),
+                detect_training_shots.run_if(resource_exists::<Training>),
+                count_practice_returns.run_if(resource_exists::<Practice>),
+                publish_ball_hits,
+            ).after(CollisionSet).in_set(RallySet))
+            .add_systems(FixedPostUpdate, hash_state.after(PhysicsSet::Writeback).after(RallySet))
+            .add_systems(Update, update_player_types
+                .run_if(in_state(GameState::Playing))
+                .run_if(resource_changed::<GameSettings>))
+            .add_systems(Update, update_score_display.in_set(PlayingSet))
+            .add_systems(Update, track_match_duration.in_set(RallySet))
+            .add_systems(Update, track_practice_time
+                .run_if(not(countdown_running))
+                .run_if(resource_exists::<Practice>)
+                .in_set(RallySet))
+            .add_systems(Update, update_practice_display
+                .run_if(resource_exists::<Practice>)
+                .in_set(PlayingSet))
+            .add_systems(Update, update_training_display
+                .run_if(resource_exists::<Training>)
+                .in_set(PlayingSet))
+            .add_systems(Update, start_rally
+                .run_if(not(countdown_running))
+                .in_set(ServingSet));
     }
 }
 
-pub use resources::Score;
\ No newline at end of file
+pub use resources::{Arena, Countdown, GameRng, MatchStats, Practice, Rules, Score, Series, StateHash, Training};
+pub use components::{Ball, CountdownDisplay, PracticeDisplay, ScoreDisplay, ScoreField};
+pub use constants::ball::MAX_BALL_SPEED;
\ No newline at end of file
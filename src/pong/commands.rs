@@ -0,0 +1,80 @@
+use bevy::prelude::*;
+use bevy::ecs::system::RunSystemOnce;
+use bevy_rapier2d::prelude::Velocity;
+
+use super::components::{Ball, Pong};
+use super::constants;
+use super::resources::{Rules, Score};
+use super::systems::reset_ball;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SetScoreCommand {
+    player1: u32,
+    player2: u32,
+}
+
+impl SetScoreCommand {
+    pub fn new(player1: u32, player2: u32) -> Self {
+        Self { player1, player2 }
+    }
+}
+
+impl Command for SetScoreCommand {
+    fn apply(self, world: &mut World) {
+        world.resource_mut::<Score>().set(self.player1, self.player2);
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SetBallSpeedCommand {
+    speed: f32,
+}
+
+impl SetBallSpeedCommand {
+    pub fn new(speed: f32) -> Self {
+        Self { speed: speed.clamp(0., constants::ball::MAX_BALL_SPEED) }
+    }
+}
+
+impl Command for SetBallSpeedCommand {
+    fn apply(self, world: &mut World) {
+        let mut balls = world.query_filtered::<&mut Velocity, With<Ball>>();
+        for mut velocity in balls.iter_mut(world) {
+            velocity.linvel = velocity.linvel.normalize_or(Vec2::X) * self.speed;
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResetBallCommand;
+
+impl Command for ResetBallCommand {
+    fn apply(self, world: &mut World) {
+        let has_game = world.query_filtered::<(), With<Pong>>().iter(world).next().is_some();
+        if !has_game {
+            warn!("Cannot reset the ball outside of a match");
+            return;
+        }
+
+        if let Err(error) = world.run_system_once(reset_ball) {
+            warn!("Could not reset ball: {}", error);
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SetTargetScoreCommand {
+    target_score: u32,
+}
+
+impl SetTargetScoreCommand {
+    pub fn new(target_score: u32) -> Self {
+        Self { target_score: target_score.max(1) }
+    }
+}
+
+impl Command for SetTargetScoreCommand {
+    fn apply(self, world: &mut World) {
+        world.resource_mut::<Rules>().target_score = self.target_score;
+    }
+}
\ No newline at end of file
@@ -7,6 +7,18 @@ pub struct Pong;
 pub struct Ball;
 
 #[derive(Component)]
+pub struct ScoreDisplay;
+
+#[derive(Component)]
+pub struct CountdownDisplay;
+
+#[derive(Component)]
+pub struct PracticeDisplay;
+
+#[derive(Component)]
+pub struct TargetWall;
+
+#[derive(Component, PartialEq)]
 pub enum ScoreField {
     Left,
     Right,
@@ -5,6 +5,15 @@ pub const TOP_BUFFER: f32 = 100.0;
      pub const MAX_SCORE: u32 = 5;
  }
 
+pub mod training {
+    pub const LAUNCHER_OFFSET: f32 = 100.0;
+}
+
+pub mod countdown {
+    pub const SECONDS: u32 = 3;
+    pub const GO_DURATION: f32 = 0.5;
+}
+
 pub mod paddle {
     pub const WIDTH: f32 = 10.0;
     pub const HEIGHT: f32 = 100.0;
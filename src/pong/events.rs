@@ -0,0 +1,90 @@
+//! Gameplay events published during a match.
+//!
+//! Every public event below is both sent as a buffered event (read it with an
+//! `EventReader`) and triggered globally (observe it with `App::add_observer`),
+//! so plugins can follow a match without touching the pong systems.
+
+use bevy::prelude::*;
+
+use crate::game::settings::{GameMode, PlayerType};
+use super::resources::MatchStats;
+
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CollisionSet;
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct BallHitPaddle {
+    pub ball: Entity,
+    pub paddle: Entity,
+    pub offset: f32,
+}
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct BallHitWall {
+    pub ball: Entity,
+    pub wall: Entity,
+}
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct BallEnteredGoal {
+    pub ball: Entity,
+    pub goal: Entity,
+}
+
+/// A match was set up and the first serve is about to start.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct MatchStarted {
+    pub mode: GameMode,
+    pub player1: PlayerType,
+    pub player2: PlayerType,
+    pub target_score: u32,
+}
+
+/// The countdown finished and the ball is in play.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ServeStarted {
+    pub score: (u32, u32),
+}
+
+/// A paddle returned the ball. `offset` runs from -1 (bottom edge) to 1 (top edge).
+#[derive(Event, Debug, Clone, Copy)]
+pub struct PaddleHit {
+    pub player_num: usize,
+    pub offset: f32,
+    pub speed: f32,
+}
+
+/// The ball bounced off a wall.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct WallBounce {
+    pub speed: f32,
+}
+
+/// A point was scored. `score` already includes the new point.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct PointScored {
+    pub scorer: usize,
+    pub conceder: usize,
+    pub score: (u32, u32),
+}
+
+/// `player` can win the match with the next point. Sent once per player per match.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct MatchPointReached {
+    pub player: usize,
+    pub score: (u32, u32),
+    pub target_score: u32,
+}
+
+/// The match is over. `winner` is `None` when it was ended early on a tied score.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct MatchEnded {
+    pub winner: Option<usize>,
+    pub score: (u32, u32),
+    pub stats: MatchStats,
+}
+
+pub(crate) fn publish<E: Event + Clone>(commands: &mut Commands, event: E) {
+    commands.send_event(event.clone());
+    commands.trigger(event);
+}
\ No newline at end of file
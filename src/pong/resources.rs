@@ -1,8 +1,71 @@
 use bevy::prelude::*;
-use crate::pong::constants::game::MAX_SCORE;
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use crate::game::drills::{Drill, DrillStats};
+use crate::pong::constants::{countdown, game::MAX_SCORE};
+use crate::locale::Translations;
 use super::components::ScoreField;
 
+/// Source of all gameplay randomness, so seeded matches can be replayed.
 #[derive(Resource)]
+pub struct GameRng(StdRng);
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self(StdRng::seed_from_u64(seed))
+    }
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self(StdRng::from_entropy())
+    }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.0.try_fill_bytes(dest)
+    }
+}
+
+/// Hash of the ball and paddle positions and velocities and the score after the latest
+/// physics tick. `tick` only counts ticks and is not part of `value`, so equal states
+/// hash equally. Runs with the same seed and inputs produce the same hash on every tick.
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct StateHash {
+    pub tick: u64,
+    pub value: u64,
+}
+
+#[derive(Resource, Default, Clone, Copy)]
+pub struct Arena {
+    pub size: Option<Vec2>,
+}
+
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct Rules {
+    pub target_score: u32,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self { target_score: MAX_SCORE }
+    }
+}
+
+#[derive(Resource, Default)]
 pub struct Score {
     player1: u32,
     player2: u32,
@@ -14,6 +77,11 @@ impl Score {
         self.player2 = 0;
     }
 
+    pub fn set(&mut self, player1: u32, player2: u32) {
+        self.player1 = player1;
+        self.player2 = player2;
+    }
+
     pub fn add_point(&mut self, field: &ScoreField) {
         match field {
             ScoreField::Right => self.player1 += 1,
@@ -21,25 +89,270 @@ impl Score {
         }
     }
 
+    pub fn points(&self) -> (u32, u32) {
+        (self.player1, self.player2)
+    }
+
     pub fn display_text(&self) -> String {
         format!("{} - {}", self.player1, self.player2)
     }
 
-    pub fn is_game_end(&self) -> bool {
-        self.player1 >= MAX_SCORE || self.player2 >= MAX_SCORE
+    pub fn is_game_end(&self, rules: &Rules) -> bool {
+        self.player1 >= rules.target_score || self.player2 >= rules.target_score
+    }
+
+    pub fn is_match_point(&self, rules: &Rules) -> bool {
+        self.player1 + 1 >= rules.target_score || self.player2 + 1 >= rules.target_score
     }
 
-    pub fn get_winner(&self) -> String {
-        if self.player1 >= MAX_SCORE { "Player 1".into() }
-        else { "Player 2".into() }
+    /// `None` for a tie, which only happens when a match is ended early.
+    pub fn get_winner_num(&self) -> Option<usize> {
+        match self.player1.cmp(&self.player2) {
+            std::cmp::Ordering::Greater => Some(1),
+            std::cmp::Ordering::Less => Some(2),
+            std::cmp::Ordering::Equal => None,
+        }
     }
 }
 
-impl Default for Score {
+#[derive(Resource)]
+pub struct Countdown {
+    timer: Timer,
+}
+
+impl Default for Countdown {
     fn default() -> Self {
         Self {
-            player1: 0,
-            player2: 0,
+            timer: Timer::from_seconds(countdown::SECONDS as f32 + countdown::GO_DURATION, TimerMode::Once),
         }
     }
+}
+
+impl Countdown {
+    pub fn tick(&mut self, delta: std::time::Duration) {
+        self.timer.tick(delta);
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.timer.elapsed_secs() < countdown::SECONDS as f32
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.timer.finished()
+    }
+
+    pub fn display_text(&self, tr: &Translations) -> String {
+        if self.is_running() {
+            (countdown::SECONDS - self.timer.elapsed_secs() as u32).to_string()
+        } else {
+            tr.text("countdown.go")
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct Series {
+    best_of: u32,
+    player1: u32,
+    player2: u32,
+}
+
+impl Series {
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn record(&mut self, winner_num: usize, best_of: u32) {
+        if self.best_of != best_of || self.is_decided() {
+            *self = Self { best_of, ..default() };
+        }
+
+        match winner_num {
+            1 => self.player1 += 1,
+            _ => self.player2 += 1,
+        }
+    }
+
+    pub fn swap(&mut self) {
+        std::mem::swap(&mut self.player1, &mut self.player2);
+    }
+
+    pub fn best_of(&self) -> u32 {
+        self.best_of
+    }
+
+    pub fn wins(&self) -> (u32, u32) {
+        (self.player1, self.player2)
+    }
+
+    pub fn is_decided(&self) -> bool {
+        let needed = self.best_of / 2 + 1;
+        self.player1 >= needed || self.player2 >= needed
+    }
+
+    pub fn get_winner_num(&self) -> Option<usize> {
+        match self.player1.cmp(&self.player2) {
+            std::cmp::Ordering::Greater => Some(1),
+            std::cmp::Ordering::Less => Some(2),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct Practice {
+    returns: u32,
+    time: f32,
+    best_returns: u32,
+    best_time: f32,
+}
+
+impl Practice {
+    pub fn new(best_returns: u32, best_time: f32) -> Self {
+        Self {
+            best_returns,
+            best_time,
+            ..default()
+        }
+    }
+
+    pub fn add_return(&mut self) {
+        self.returns += 1;
+    }
+
+    pub fn tick(&mut self, seconds: f32) {
+        self.time += seconds;
+    }
+
+    pub fn miss(&mut self) {
+        self.best_returns = self.best_returns.max(self.returns);
+        self.best_time = self.best_time.max(self.time);
+        self.returns = 0;
+        self.time = 0.;
+    }
+
+    pub fn best(&self) -> (u32, f32) {
+        (self.best_returns, self.best_time)
+    }
+
+    pub fn display_text(&self) -> String {
+        format!("{} - {:.0}s", self.returns, self.time)
+    }
+
+    pub fn best_text(&self, tr: &Translations) -> String {
+        let (returns, time) = (self.best_returns.to_string(), format!("{:.1}", self.best_time));
+        tr.format("practice.best", &[("returns", &returns), ("time", &time)])
+    }
+}
+
+#[derive(Resource)]
+pub struct Training {
+    drill: Drill,
+    stats: DrillStats,
+    launcher: Timer,
+    launcher_position: Vec2,
+    arena_height: f32,
+    in_flight: bool,
+    returned: bool,
+}
+
+impl Training {
+    pub fn new(drill: Drill, launcher_position: Vec2, arena_height: f32) -> Self {
+        Self {
+            launcher: Timer::from_seconds(drill.interval, TimerMode::Once),
+            drill,
+            stats: DrillStats::default(),
+            launcher_position,
+            arena_height,
+            in_flight: false,
+            returned: false,
+        }
+    }
+
+    pub fn drill(&self) -> &Drill {
+        &self.drill
+    }
+
+    pub fn stats(&self) -> &DrillStats {
+        &self.stats
+    }
+
+    pub fn launcher_position(&self) -> Vec2 {
+        self.launcher_position
+    }
+
+    pub fn is_in_flight(&self) -> bool {
+        self.in_flight
+    }
+
+    pub fn is_curving(&self) -> bool {
+        self.in_flight && !self.returned
+    }
+
+    pub fn tick(&mut self, delta: std::time::Duration) -> bool {
+        if self.in_flight {
+            return false;
+        }
+
+        self.launcher.tick(delta);
+        self.launcher.finished()
+    }
+
+    pub fn launch(&mut self) {
+        self.in_flight = true;
+        self.returned = false;
+        self.stats.launched += 1;
+    }
+
+    pub fn add_return(&mut self) {
+        if self.is_curving() {
+            self.returned = true;
+            self.stats.returns += 1;
+        }
+    }
+
+    pub fn hit_target_wall(&mut self, ball_y: f32) {
+        if self.returned && self.drill.target.contains(ball_y - self.launcher_position.y, self.arena_height) {
+            self.stats.on_target += 1;
+        }
+        self.finish();
+    }
+
+    pub fn finish(&mut self) {
+        self.in_flight = false;
+        self.returned = false;
+        self.launcher.reset();
+    }
+
+    pub fn display_text(&self, tr: &Translations) -> String {
+        tr.format("training.hud", &[
+            ("returns", &self.stats.returns.to_string()),
+            ("launched", &self.stats.launched.to_string()),
+            ("accuracy", &format!("{:.0}", self.stats.accuracy())),
+        ])
+    }
+}
+
+#[derive(Resource, Default, Debug, Clone, Copy)]
+pub struct MatchStats {
+    pub paddle_hits: (u32, u32),
+    pub wall_bounces: u32,
+    pub rally_hits: u32,
+    pub longest_rally: u32,
+    pub duration: f32,
+}
+
+impl MatchStats {
+    pub fn add_paddle_hit(&mut self, player_num: usize) {
+        match player_num {
+            1 => self.paddle_hits.0 += 1,
+            _ => self.paddle_hits.1 += 1,
+        }
+        self.rally_hits += 1;
+        self.longest_rally = self.longest_rally.max(self.rally_hits);
+    }
+
+    pub fn end_rally(&mut self) {
+        self.rally_hits = 0;
+    }
 }
\ No newline at end of file
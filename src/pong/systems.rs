@@ -1,39 +1,65 @@
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
-use bevy_rapier2d::rapier::prelude::CollisionEventFlags;
 use leafwing_input_manager::prelude::*;
 
+use rand::Rng;
 use std::f32::consts::PI;
 
 use crate::game::controls::GameAction;
-use crate::game::states::PausedState;
-use crate::game::settings::{GameSettings, Difficulty, PlayerType};
-
-use super::Score;
+use crate::game::states::{GameState, MatchPointState, MatchState, PausedState};
+use crate::game::settings::{GameSettings, GameMode, Difficulty, PlayerType};
+use crate::game::profiles::PlayerProfiles;
+use crate::game::drills::{DrillPresets, SelectedDrill};
+use crate::locale::Translations;
+
+use super::{Rules, Score};
+use super::resources::{Arena, Countdown, GameRng, MatchStats, Practice, Series, StateHash, Training};
 use super::components::*;
 use super::constants;
+use super::events::{
+    publish, BallEnteredGoal, BallHitPaddle, BallHitWall,
+    MatchStarted, ServeStarted, PaddleHit, WallBounce, MatchPointReached, MatchEnded,
+};
 use super::observers::OnPointScored;
 
 
 pub mod setup {
     use super::*;
 
+    #[allow(clippy::too_many_arguments)]
     pub fn game(
         mut commands: Commands,
         windows: Query<&Window>,
+        arena: Res<Arena>,
         mut meshes: ResMut<Assets<Mesh>>,
         mut materials: ResMut<Assets<ColorMaterial>>,
         mut score: ResMut<Score>,
         mut next_state: ResMut<NextState<PausedState>>,
         settings: Res<GameSettings>,
+        profiles: Res<PlayerProfiles>,
+        drill: Res<SelectedDrill>,
     ) {
         score.reset();
         next_state.set(PausedState::Playing);
 
-        let window = windows.single();
-        let (width, height) = (window.resolution.width(), window.resolution.height());
+        if settings.get_mode() == GameMode::Practice {
+            let (best_returns, best_time) = settings.get_profile(1)
+                .and_then(|name| profiles.get(name))
+                .map(|profile| (profile.stats.practice_best_returns, profile.stats.practice_best_time))
+                .unwrap_or_default();
+            commands.insert_resource(Practice::new(best_returns, best_time));
+        }
+
+        let size = arena.size.unwrap_or_else(|| windows.single().resolution.size());
+        let (width, height) = (size.x, size.y);
 
-        spawn_game_world(&mut commands, width, height, &mut meshes, &mut materials, settings);
+        if settings.get_mode() == GameMode::Training {
+            let launcher = Vec2::new(width / 2.0 - constants::training::LAUNCHER_OFFSET, constants::TOP_BUFFER / -2.0);
+            let arena_height = height - constants::TOP_BUFFER - constants::WALL_THICKNESS;
+            commands.insert_resource(Training::new(drill.0.clone(), launcher, arena_height));
+        }
+
+        spawn_game_world(&mut commands, width, height, &mut meshes, &mut materials, &settings, &profiles);
     }
 
     fn spawn_game_world(
@@ -42,35 +68,41 @@ pub mod setup {
         height: f32,
         meshes: &mut ResMut<Assets<Mesh>>,
         materials: &mut ResMut<Assets<ColorMaterial>>,
-        settings: Res<GameSettings>,
+        settings: &GameSettings,
+        profiles: &PlayerProfiles,
     ) {
         commands.spawn((
             Pong,
             Transform::default(),
             Visibility::default(),
         )).with_children(|builder| {
-            create_board(builder, width, height, meshes, materials);
-            create_players(builder, width, meshes, materials, settings);
+            create_board(builder, width, height, meshes, materials, settings.get_mode());
+            create_players(builder, width, meshes, materials, settings, profiles);
             spawn_ball(builder, meshes, materials);
             create_score(builder, height);
+            create_countdown(builder);
+            create_player_names(builder, width, height, settings);
+            if settings.get_mode() == GameMode::Practice {
+                create_practice_best(builder, width, height);
+            }
         });
     }
 
-    fn create_wall(
-        commands: &mut ChildBuilder,
+    fn create_wall<'a>(
+        commands: &'a mut ChildBuilder,
         meshes: &mut ResMut<Assets<Mesh>>,
         materials: &mut ResMut<Assets<ColorMaterial>>,
         width: f32,
         height: f32,
         transform: Transform
-    ) {
+    ) -> EntityCommands<'a> {
         commands.spawn((
             Mesh2d(meshes.add(Rectangle::new(width, height))),
             MeshMaterial2d(materials.add(Color::WHITE)),
             transform,
             Collider::cuboid(width / 2.0, height / 2.0),
             RigidBody::Fixed,
-        ));
+        ))
     }
 
     fn create_board(
@@ -79,6 +111,7 @@ pub mod setup {
         screen_height: f32,
         meshes: &mut ResMut<Assets<Mesh>>,
         materials: &mut ResMut<Assets<ColorMaterial>>,
+        mode: GameMode,
     ) {
         // Create horizontal walls
         for y_pos in [
@@ -101,6 +134,19 @@ pub mod setup {
             (screen_width / -2.0 + constants::WALL_THICKNESS, ScoreField::Left),
             (screen_width / 2.0 - constants::WALL_THICKNESS, ScoreField::Right),
         ] {
+            // Without an opponent the right side is a solid wall
+            if mode != GameMode::Versus && matches!(score_field, ScoreField::Right) {
+                create_wall(
+                    builder,
+                    meshes,
+                    materials,
+                    constants::WALL_THICKNESS,
+                    sensor_height,
+                    Transform::from_xyz(x_pos, constants::TOP_BUFFER / -2.0, 0.0),
+                ).insert(TargetWall);
+                continue;
+            }
+
             builder.spawn((
                 Transform::from_xyz(
                     x_pos,
@@ -121,13 +167,14 @@ pub mod setup {
         transform: Transform,
         player_type: PlayerType,
         score_field: ScoreField,
+        color: Color,
     ) {
         builder.spawn((
             Mesh2d(meshes.add(Rectangle::new(
                 constants::paddle::WIDTH,
                 constants::paddle::HEIGHT,
             ))),
-            MeshMaterial2d(materials.add(Color::WHITE)),
+            MeshMaterial2d(materials.add(color)),
             transform,
             Collider::cuboid(constants::paddle::WIDTH / 2.0, constants::paddle::HEIGHT / 2.0),
             RigidBody::KinematicPositionBased,
@@ -142,19 +189,36 @@ pub mod setup {
         screen_width: f32,
         meshes: &mut ResMut<Assets<Mesh>>,
         materials: &mut ResMut<Assets<ColorMaterial>>,
-        settings: Res<GameSettings>,
+        settings: &GameSettings,
+        profiles: &PlayerProfiles,
     ) {
-        for (x_offset, player_type, score_field) in [
-            (screen_width / -2.0 + constants::paddle::BUFFER, settings.get_player1(), ScoreField::Left),
-            (screen_width / 2.0 - constants::paddle::BUFFER, settings.get_player2(), ScoreField::Right),
+        for (player_num, x_offset, player_type, score_field) in [
+            (1, screen_width / -2.0 + constants::paddle::BUFFER, settings.get_player1(), ScoreField::Left),
+            (2, screen_width / 2.0 - constants::paddle::BUFFER, settings.get_player2(), ScoreField::Right),
         ] {
+            if settings.get_mode() != GameMode::Versus && player_num == 2 {
+                continue;
+            }
+
+            // Practice is a solo drill, so the remaining paddle is always the player's
+            let player_type = match settings.get_mode() {
+                GameMode::Practice => PlayerType::Human,
+                _ => *player_type,
+            };
+
+            let color = settings.get_profile(player_num)
+                .and_then(|name| profiles.get(name))
+                .map(|profile| profile.color)
+                .unwrap_or(Color::WHITE);
+
             create_paddle(
                 builder,
                 meshes,
                 materials,
                 Transform::from_xyz(x_offset, constants::TOP_BUFFER / -2.0, 0.0),
-                player_type.clone(),
+                player_type,
                 score_field,
+                color,
             );
         }
     }
@@ -165,6 +229,40 @@ pub mod setup {
             TextColor(Color::WHITE),
             TextFont { font_size: 100., ..default() },
             Transform::from_translation((window_height / 2.0 - 50.) * Vec3::Y),
+            ScoreDisplay,
+        ));
+    }
+
+    fn create_countdown(builder: &mut ChildBuilder) {
+        builder.spawn((
+            Text2d::default(),
+            TextColor(Color::WHITE),
+            TextFont { font_size: 150., ..default() },
+            Transform::from_xyz(0.0, constants::TOP_BUFFER / -2.0, 1.0),
+            CountdownDisplay,
+        ));
+    }
+
+    fn create_player_names(builder: &mut ChildBuilder, screen_width: f32, screen_height: f32, settings: &GameSettings) {
+        for (player_num, x_pos) in [(1, screen_width / -4.0), (2, screen_width / 4.0)] {
+            if let Some(name) = settings.get_profile(player_num) {
+                builder.spawn((
+                    Text2d::new(name),
+                    TextColor(Color::WHITE),
+                    TextFont { font_size: 40., ..default() },
+                    Transform::from_xyz(x_pos, screen_height / 2.0 - 50., 0.0),
+                ));
+            }
+        }
+    }
+
+    fn create_practice_best(builder: &mut ChildBuilder, screen_width: f32, screen_height: f32) {
+        builder.spawn((
+            Text2d::default(),
+            TextColor(Color::WHITE),
+            TextFont { font_size: 40., ..default() },
+            Transform::from_xyz(screen_width / 4.0, screen_height / 2.0 - 50., 0.0),
+            PracticeDisplay,
         ));
     }
 
@@ -217,6 +315,15 @@ pub mod movement {
         }
     }
 
+    pub fn update_types(settings: Res<GameSettings>, mut players: Query<(&mut PlayerType, &ScoreField)>) {
+        for (mut player_type, score_field) in players.iter_mut() {
+            *player_type = match score_field {
+                ScoreField::Left => *settings.get_player1(),
+                ScoreField::Right => *settings.get_player2(),
+            };
+        }
+    }
+
     fn handle_player_input(
         mut player: Mut<KinematicCharacterController>,
         score_field: &ScoreField,
@@ -252,9 +359,10 @@ pub mod movement {
         ball: &Transform,
         difficulty: Difficulty,
     ) {
+        let target = ball.translation.y - difficulty.aim() * constants::paddle::HEIGHT / 2.0;
         let direction = Vec2::new(
             0.0,
-            ball.translation.y - paddle_position.translation.y,
+            target - paddle_position.translation.y,
         );
 
         player.translation = Some(
@@ -263,29 +371,50 @@ pub mod movement {
     }
 }
 
-pub mod scoring {
+pub mod collisions {
     use super::*;
 
-    pub fn detect_point(
-        mut commands: Commands,
+    pub fn classify(
         mut collision_events: EventReader<CollisionEvent>,
-        mut walls_query: Query<Entity, (With<ScoreField>, Without<PlayerType>)>
+        balls: Query<&Transform, With<Ball>>,
+        paddles: Query<&Transform, With<PlayerType>>,
+        goals: Query<(), (With<ScoreField>, Without<PlayerType>)>,
+        mut paddle_hits: EventWriter<BallHitPaddle>,
+        mut wall_hits: EventWriter<BallHitWall>,
+        mut goals_entered: EventWriter<BallEnteredGoal>,
     ) {
         for event in collision_events.read() {
-            let (entity1, entity2, flags) = match event {
-                CollisionEvent::Started(e1, e2, flags) => (e1, e2, flags),
-                CollisionEvent::Stopped(_, _, _) => continue,
+            let CollisionEvent::Started(entity1, entity2, _) = event else {
+                continue;
             };
 
-            if *flags & CollisionEventFlags::SENSOR != CollisionEventFlags::SENSOR { continue; }
+            let (ball, other) = if balls.contains(*entity1) { (*entity1, *entity2) } else { (*entity2, *entity1) };
+            let Ok(ball_transform) = balls.get(ball) else {
+                continue;
+            };
 
-            if let Ok(wall) = walls_query.get(*entity1).or_else(|_| walls_query.get(*entity2)) {
-                commands.trigger(OnPointScored(wall));
+            if let Ok(paddle) = paddles.get(other) {
+                let offset = (ball_transform.translation.y - paddle.translation.y) / (constants::paddle::HEIGHT / 2.0);
+                paddle_hits.send(BallHitPaddle { ball, paddle: other, offset });
+            } else if goals.contains(other) {
+                goals_entered.send(BallEnteredGoal { ball, goal: other });
+            } else {
+                wall_hits.send(BallHitWall { ball, wall: other });
             }
         }
     }
+}
 
-    pub fn update_display(score: Res<Score>, mut score_text: Query<&mut Text2d>) {
+pub mod scoring {
+    use super::*;
+
+    pub fn detect_point(mut commands: Commands, mut goals_entered: EventReader<BallEnteredGoal>) {
+        for event in goals_entered.read() {
+            commands.trigger(OnPointScored(event.goal));
+        }
+    }
+
+    pub fn update_display(score: Res<Score>, mut score_text: Query<&mut Text2d, With<ScoreDisplay>>) {
         if score.is_changed() {
             for mut text in &mut score_text {
                 text.0 = score.display_text();
@@ -296,17 +425,31 @@ pub mod scoring {
 
 pub mod ball {
     use super::*;
+
+    /// Parks the ball outside the physics step, so the paddles keep moving while it waits for the serve.
+    pub fn hold(mut commands: Commands, balls: Query<Entity, (With<Ball>, Without<RigidBodyDisabled>)>) {
+        for ball in &balls {
+            commands.entity(ball).insert(RigidBodyDisabled);
+        }
+    }
+
+    pub fn release(mut commands: Commands, balls: Query<Entity, (With<Ball>, With<RigidBodyDisabled>)>) {
+        for ball in &balls {
+            commands.entity(ball).remove::<RigidBodyDisabled>();
+        }
+    }
+
     pub fn speed_up(
-        mut collision_events: EventReader<CollisionEvent>,
-        mut velocities: Query<&mut Velocity>,
+        mut paddle_hits: EventReader<BallHitPaddle>,
+        mut wall_hits: EventReader<BallHitWall>,
+        mut velocities: Query<&mut Velocity, With<Ball>>,
     ) {
-        for event in collision_events.read() {
-            if let CollisionEvent::Started(entity1, entity2, _) = event {
-                if let Ok(mut velocity) = velocities.get_mut(*entity1) {
-                    adjust_velocity(&mut velocity);
-                } else if let Ok(mut velocity) = velocities.get_mut(*entity2) {
-                    adjust_velocity(&mut velocity);
-                }
+        let balls = paddle_hits.read().map(|hit| hit.ball)
+            .chain(wall_hits.read().map(|hit| hit.ball));
+
+        for ball in balls {
+            if let Ok(mut velocity) = velocities.get_mut(ball) {
+                adjust_velocity(&mut velocity);
             }
         }
     }
@@ -317,42 +460,471 @@ pub mod ball {
     }
 
     pub fn paddle_collision(
-        mut collision_events: EventReader<CollisionEvent>,
-        mut ball_query: Query<(&Transform, &mut Velocity), With<Ball>>,
-        paddle_query: Query<&Transform, With<PlayerType>>,
+        mut paddle_hits: EventReader<BallHitPaddle>,
+        mut ball_query: Query<&mut Velocity, With<Ball>>,
     ) {
-        for event in collision_events.read() {
-            if let CollisionEvent::Started(entity1, entity2, _) = event {
-                if let Ok(paddle) = paddle_query.get(*entity1).or_else(|_| paddle_query.get(*entity2)) {
+        for hit in paddle_hits.read() {
+            let Ok(mut ball_velocity) = ball_query.get_mut(hit.ball) else {
+                continue;
+            };
+
+            let angle = hit.offset * PI / 2.0;
+            let speed = ball_velocity.linvel.length();
+
+            ball_velocity.linvel.x = -ball_velocity.linvel.x;
+            ball_velocity.linvel.y = angle * speed;
+
+            ball_velocity.linvel = ball_velocity.linvel.normalize() * speed;
+        }
+    }
+}
+
+pub mod rounds {
+    use super::*;
+
+    pub fn reset_ball(
+        mut commands: Commands,
+        mut meshes: ResMut<Assets<Mesh>>,
+        mut materials: ResMut<Assets<ColorMaterial>>,
+        ball_entity: Query<Entity, With<Ball>>,
+        pong_entity: Query<Entity, With<Pong>>,
+    ) {
+        commands.entity(ball_entity.single()).despawn();
+
+        let pong = pong_entity.single();
+        commands.entity(pong).with_children(|parent| {
+            setup::spawn_ball(parent, &mut meshes, &mut materials);
+        });
+    }
+
+    pub fn next_serve(
+        score: Res<Score>,
+        rules: Res<Rules>,
+        mut next_game_state: ResMut<NextState<GameState>>,
+        mut next_match_state: ResMut<NextState<MatchState>>,
+    ) {
+        if score.is_game_end(&rules) {
+            next_game_state.set(GameState::Endgame);
+        } else {
+            next_match_state.set(MatchState::Serving);
+        }
+    }
+
+    pub fn check_match_point(
+        score: Res<Score>,
+        rules: Res<Rules>,
+        state: Res<State<MatchPointState>>,
+        mut next_state: ResMut<NextState<MatchPointState>>,
+    ) {
+        let match_point = if score.is_match_point(&rules) { MatchPointState::Active } else { MatchPointState::Inactive };
+        if *state.get() != match_point {
+            next_state.set(match_point);
+        }
+    }
+
+    pub fn start_rally(mut next_state: ResMut<NextState<MatchState>>) {
+        next_state.set(MatchState::Rally);
+    }
+}
+
+pub mod practice {
+    use super::*;
+
+    pub fn count_returns(mut paddle_hits: EventReader<BallHitPaddle>, mut practice: ResMut<Practice>) {
+        for _ in paddle_hits.read() {
+            practice.add_return();
+        }
+    }
+
+    pub fn track_time(time: Res<Time>, mut practice: ResMut<Practice>) {
+        practice.tick(time.delta_secs());
+    }
+
+    pub fn update_display(
+        practice: Res<Practice>,
+        tr: Res<Translations>,
+        mut score_text: Query<&mut Text2d, (With<ScoreDisplay>, Without<PracticeDisplay>)>,
+        mut best_text: Query<&mut Text2d, With<PracticeDisplay>>,
+    ) {
+        for mut text in &mut score_text {
+            text.0 = practice.display_text();
+        }
+        for mut text in &mut best_text {
+            text.0 = practice.best_text(&tr);
+        }
+    }
+
+    pub fn store_best(
+        practice: Res<Practice>,
+        settings: Res<GameSettings>,
+        mut profiles: ResMut<PlayerProfiles>,
+    ) {
+        let Some(profile) = settings.get_profile(1).and_then(|name| profiles.get_mut(name)) else {
+            return;
+        };
+
+        let (best_returns, best_time) = practice.best();
+        profile.stats.practice_best_returns = profile.stats.practice_best_returns.max(best_returns);
+        profile.stats.practice_best_time = profile.stats.practice_best_time.max(best_time);
+    }
+}
+
+pub mod training {
+    use super::*;
+
+    pub fn launch_ball(
+        time: Res<Time>,
+        mut rng: ResMut<GameRng>,
+        mut training: ResMut<Training>,
+        mut balls: Query<(&mut Transform, &mut Velocity), With<Ball>>,
+    ) {
+        let Ok((mut transform, mut velocity)) = balls.get_single_mut() else {
+            return;
+        };
+
+        if training.is_in_flight() {
+            return;
+        }
+
+        // Keep the ball waiting in the launcher until the next shot
+        transform.translation = training.launcher_position().extend(0.0);
+        velocity.linvel = Vec2::ZERO;
+        velocity.angvel = 0.;
+
+        if training.tick(time.delta()) {
+            let drill = training.drill();
+            let speed = rng.gen_range(drill.min_speed..=drill.max_speed.max(drill.min_speed));
+            let angle = rng.gen_range(drill.min_angle..=drill.max_angle.max(drill.min_angle)).to_radians();
+
+            velocity.linvel = Vec2::new(-angle.cos(), angle.sin()) * speed;
+            training.launch();
+        }
+    }
 
-                    let (ball_transform, mut ball_velocity) = ball_query.single_mut();
+    pub fn curve_ball(
+        time: Res<Time>,
+        training: Res<Training>,
+        mut balls: Query<&mut Velocity, With<Ball>>,
+    ) {
+        if !training.is_curving() {
+            return;
+        }
 
-                    let hit_position = (ball_transform.translation.y - paddle.translation.y) / (constants::paddle::HEIGHT / 2.0);
-                    let angle = hit_position * PI / 2.0;
-                    let speed = ball_velocity.linvel.length();
+        let rotation = Vec2::from_angle(training.drill().spin.to_radians() * time.delta_secs());
+        for mut velocity in &mut balls {
+            velocity.linvel = rotation.rotate(velocity.linvel);
+        }
+    }
 
-                    ball_velocity.linvel.x = -ball_velocity.linvel.x;
-                    ball_velocity.linvel.y = angle * speed;
+    pub fn detect_shots(
+        mut paddle_hits: EventReader<BallHitPaddle>,
+        mut wall_hits: EventReader<BallHitWall>,
+        mut goals_entered: EventReader<BallEnteredGoal>,
+        mut training: ResMut<Training>,
+        balls: Query<&Transform, With<Ball>>,
+        target_walls: Query<(), With<TargetWall>>,
+    ) {
+        for _ in paddle_hits.read() {
+            training.add_return();
+        }
 
-                    ball_velocity.linvel = ball_velocity.linvel.normalize() * speed;
-                }
+        for hit in wall_hits.read() {
+            if let (Ok(ball), true) = (balls.get(hit.ball), target_walls.contains(hit.wall)) {
+                training.hit_target_wall(ball.translation.y);
             }
         }
+
+        for _ in goals_entered.read() {
+            training.finish();
+        }
+    }
+
+    pub fn update_display(
+        training: Res<Training>,
+        tr: Res<Translations>,
+        mut score_text: Query<&mut Text2d, With<ScoreDisplay>>,
+    ) {
+        for mut text in &mut score_text {
+            text.0 = training.display_text(&tr);
+        }
+    }
+
+    pub fn record_stats(training: Res<Training>, mut presets: ResMut<DrillPresets>) {
+        presets.record(&training.drill().name, training.stats());
     }
 }
 
+pub mod publishing {
+    use super::*;
+
+    pub fn match_started(
+        mut commands: Commands,
+        settings: Res<GameSettings>,
+        rules: Res<Rules>,
+    ) {
+        commands.insert_resource(MatchStats::default());
+        commands.insert_resource(PublishedMatchPoints::default());
+        publish(&mut commands, MatchStarted {
+            mode: settings.get_mode(),
+            player1: *settings.get_player1(),
+            player2: *settings.get_player2(),
+            target_score: rules.target_score,
+        });
+    }
+
+    pub fn serve_started(mut commands: Commands, score: Res<Score>) {
+        publish(&mut commands, ServeStarted { score: score.points() });
+    }
+
+    /// Players whose match point was already published this match.
+    #[derive(Resource, Default)]
+    pub struct PublishedMatchPoints([bool; 2]);
+
+    pub fn match_point_reached(
+        mut commands: Commands,
+        score: Res<Score>,
+        rules: Res<Rules>,
+        mut published: ResMut<PublishedMatchPoints>,
+    ) {
+        let (player1, player2) = score.points();
+        for (player, points) in [(1, player1), (2, player2)] {
+            if points + 1 >= rules.target_score && !published.0[player - 1] {
+                published.0[player - 1] = true;
+                publish(&mut commands, MatchPointReached {
+                    player,
+                    score: score.points(),
+                    target_score: rules.target_score,
+                });
+            }
+        }
+    }
+
+    pub fn match_ended(mut commands: Commands, score: Res<Score>, stats: Res<MatchStats>) {
+        publish(&mut commands, MatchEnded {
+            winner: score.get_winner_num(),
+            score: score.points(),
+            stats: *stats,
+        });
+    }
+
+    pub fn ball_hits(
+        mut commands: Commands,
+        mut paddle_hits: EventReader<BallHitPaddle>,
+        mut wall_hits: EventReader<BallHitWall>,
+        mut stats: ResMut<MatchStats>,
+        paddles: Query<&ScoreField, With<PlayerType>>,
+        balls: Query<&Velocity, With<Ball>>,
+    ) {
+        let speed = |ball| balls.get(ball).map(|velocity| velocity.linvel.length()).unwrap_or_default();
+
+        for hit in paddle_hits.read() {
+            let player_num = match paddles.get(hit.paddle) {
+                Ok(ScoreField::Left) => 1,
+                Ok(ScoreField::Right) => 2,
+                Err(_) => continue,
+            };
+
+            stats.add_paddle_hit(player_num);
+            publish(&mut commands, PaddleHit { player_num, offset: hit.offset, speed: speed(hit.ball) });
+        }
+
+        for hit in wall_hits.read() {
+            stats.wall_bounces += 1;
+            publish(&mut commands, WallBounce { speed: speed(hit.ball) });
+        }
+    }
+
+    pub fn end_rally(mut stats: ResMut<MatchStats>) {
+        stats.end_rally();
+    }
+
+    pub fn track_duration(time: Res<Time>, mut stats: ResMut<MatchStats>) {
+        stats.duration += time.delta_secs();
+    }
+}
+
+pub mod countdown {
+    use super::*;
+
+    pub fn start(mut commands: Commands) {
+        commands.insert_resource(Countdown::default());
+    }
+
+    pub fn update(
+        mut commands: Commands,
+        time: Res<Time>,
+        mut countdown: ResMut<Countdown>,
+        mut display: Query<&mut Text2d, With<CountdownDisplay>>,
+        tr: Res<Translations>,
+    ) {
+        countdown.tick(time.delta());
+
+        let text = if countdown.is_finished() {
+            commands.remove_resource::<Countdown>();
+            String::new()
+        } else {
+            countdown.display_text(&tr)
+        };
+
+        for mut display in &mut display {
+            display.0.clone_from(&text);
+        }
+    }
+
+    pub fn is_running(countdown: Option<Res<Countdown>>) -> bool {
+        countdown.is_some_and(|countdown| countdown.is_running())
+    }
+}
+
+pub mod determinism {
+    use super::*;
+
+    /// 64-bit FNV-1a over little-endian bytes, so hashes match across runs,
+    /// Rust versions and platforms.
+    struct Fnv(u64);
+
+    impl Fnv {
+        fn new() -> Self {
+            Self(0xcbf2_9ce4_8422_2325)
+        }
+
+        fn write(&mut self, bytes: &[u8]) {
+            for byte in bytes {
+                self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3);
+            }
+        }
+
+        fn write_u32(&mut self, value: u32) {
+            self.write(&value.to_le_bytes());
+        }
+
+        fn write_vec(&mut self, vector: Vec3) {
+            for component in vector.to_array() {
+                self.write_u32(component.to_bits());
+            }
+        }
+    }
+
+    pub fn hash_state(
+        mut state_hash: ResMut<StateHash>,
+        score: Res<Score>,
+        balls: Query<(&Transform, &Velocity), With<Ball>>,
+        paddles: Query<(&Transform, Option<&KinematicCharacterControllerOutput>, &ScoreField), With<PlayerType>>,
+    ) {
+        let mut hasher = Fnv::new();
+        let (player1, player2) = score.points();
+        hasher.write_u32(player1);
+        hasher.write_u32(player2);
+
+        for (transform, velocity) in &balls {
+            hasher.write_vec(transform.translation);
+            hasher.write_vec(velocity.linvel.extend(velocity.angvel));
+        }
+
+        // Query order is not stable between runs, so hash paddles left to right
+        let mut paddles: Vec<_> = paddles.iter().collect();
+        paddles.sort_by_key(|(_, _, field)| matches!(field, ScoreField::Right));
+        for (transform, output, _) in paddles {
+            let movement = output.map(|output| output.effective_translation).unwrap_or_default();
+            hasher.write_vec(transform.translation);
+            hasher.write_vec(movement.extend(0.));
+        }
+
+        state_hash.tick += 1;
+        state_hash.value = hasher.0;
+    }
+}
+
+pub fn record_profile_stats(
+    score: Res<Score>,
+    settings: Res<GameSettings>,
+    mut profiles: ResMut<PlayerProfiles>,
+) {
+    let (player1, player2) = score.points();
+    // A forced ending on a tie counts as played, but not as won
+    let winner = score.get_winner_num();
+
+    for (player_num, scored, conceded) in [(1, player1, player2), (2, player2, player1)] {
+        let Some(profile) = settings.get_profile(player_num).and_then(|name| profiles.get_mut(name)) else {
+            continue;
+        };
+
+        profile.stats.matches_played += 1;
+        profile.stats.points_scored += scored;
+        profile.stats.points_conceded += conceded;
+        if winner == Some(player_num) {
+            profile.stats.matches_won += 1;
+        }
+    }
+}
+
+pub fn record_series_result(score: Res<Score>, settings: Res<GameSettings>, mut series: ResMut<Series>) {
+    // A game without a winner doesn't count towards the series
+    if let Some(winner) = score.get_winner_num() {
+        series.record(winner, settings.get_series_length());
+    }
+}
+
+pub fn reset_series(mut series: ResMut<Series>) {
+    series.reset();
+}
+
 pub fn cleanup_game(mut commands: Commands, pong: Query<Entity, With<Pong>>) {
+    commands.remove_resource::<Countdown>();
+    commands.remove_resource::<Practice>();
+    commands.remove_resource::<Training>();
     for entity in pong.iter() {
         commands.entity(entity).despawn_recursive();
     }
 }
 
 pub use setup::game as setup_game;
-pub use movement::players as move_players;
+pub use movement::{
+    players as move_players,
+    update_types as update_player_types,
+};
 pub use ball::{
     speed_up as speed_up_ball,
     paddle_collision as ball_paddle_collision,
+    hold as hold_ball,
+    release as release_ball,
+};
+pub use rounds::{
+    reset_ball,
+    next_serve,
+    check_match_point,
+    start_rally,
+};
+pub use practice::{
+    count_returns as count_practice_returns,
+    track_time as track_practice_time,
+    update_display as update_practice_display,
+    store_best as store_practice_best,
+};
+pub use training::{
+    launch_ball as launch_training_ball,
+    curve_ball as curve_training_ball,
+    detect_shots as detect_training_shots,
+    update_display as update_training_display,
+    record_stats as record_training_stats,
+};
+pub use publishing::{
+    match_started as publish_match_started,
+    serve_started as publish_serve_started,
+    match_point_reached as publish_match_point_reached,
+    PublishedMatchPoints,
+    match_ended as publish_match_ended,
+    ball_hits as publish_ball_hits,
+    end_rally as end_stats_rally,
+    track_duration as track_match_duration,
+};
+pub use countdown::{
+    start as start_countdown,
+    update as update_countdown,
+    is_running as countdown_running,
 };
+pub use collisions::classify as classify_collisions;
+pub use determinism::hash_state;
 pub use scoring::{
     detect_point,
     update_display as update_score_display,
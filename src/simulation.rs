@@ -0,0 +1,237 @@
+//! Batch AI-versus-AI simulation used to calibrate the computer difficulties.
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::Velocity;
+use rand::Rng;
+use serde::Serialize;
+use std::thread;
+
+use crate::game::settings::{Difficulty, GameMode, GameSettings, PlayerType};
+use crate::game::states::{GameState, MatchState, RallySet};
+use crate::pong::events::{MatchEnded, PointScored, ServeStarted};
+use crate::pong::{Ball, GameRng, MatchStats, Rules};
+use crate::{GamePlugin, HeadlessPlugin, PongPlugin};
+
+/// Rallies longer than this are replayed, as both paddles keep up indefinitely.
+const MAX_RALLY_SECONDS: f32 = 30.;
+/// Matches with more replayed rallies than this are reported as unfinished.
+const MAX_STALLED_POINTS: u32 = 10;
+/// Largest serve angle from the horizontal, in degrees.
+const MAX_SERVE_ANGLE: f32 = 50.;
+
+#[derive(Clone, Copy, Debug)]
+pub struct BatchOptions {
+    pub matches: u32,
+    pub target_score: u32,
+    pub arena_size: Vec2,
+    pub seed: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PairingReport {
+    pub player1: Difficulty,
+    pub player2: Difficulty,
+    pub matches: u32,
+    pub wins: (u32, u32),
+    pub unfinished: u32,
+    pub points: u32,
+    pub stalled_points: u32,
+    pub rally_hits: u32,
+    pub point_seconds: f32,
+}
+
+impl PairingReport {
+    pub fn win_rate(&self, player_num: usize) -> f32 {
+        let wins = if player_num == 1 { self.wins.0 } else { self.wins.1 };
+        if self.matches == 0 { 0. }
+        else { wins as f32 / self.matches as f32 }
+    }
+
+    pub fn average_rally_length(&self) -> f32 {
+        if self.points == 0 { 0. }
+        else { self.rally_hits as f32 / self.points as f32 }
+    }
+
+    pub fn average_point_duration(&self) -> f32 {
+        if self.points == 0 { 0. }
+        else { self.point_seconds / self.points as f32 }
+    }
+
+    fn row(&self) -> ReportRow {
+        ReportRow {
+            player1: self.player1.to_string(),
+            player2: self.player2.to_string(),
+            matches: self.matches,
+            player1_wins: self.wins.0,
+            player2_wins: self.wins.1,
+            unfinished: self.unfinished,
+            player1_win_rate: self.win_rate(1),
+            player2_win_rate: self.win_rate(2),
+            average_rally_length: self.average_rally_length(),
+            average_point_duration: self.average_point_duration(),
+            stalled_points: self.stalled_points,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ReportRow {
+    player1: String,
+    player2: String,
+    matches: u32,
+    player1_wins: u32,
+    player2_wins: u32,
+    unfinished: u32,
+    player1_win_rate: f32,
+    player2_win_rate: f32,
+    average_rally_length: f32,
+    average_point_duration: f32,
+    stalled_points: u32,
+}
+
+const CSV_HEADER: &str = "player1,player2,matches,player1_wins,player2_wins,unfinished,\
+player1_win_rate,player2_win_rate,average_rally_length,average_point_duration,stalled_points";
+
+pub fn to_csv(reports: &[PairingReport]) -> String {
+    let mut csv = CSV_HEADER.to_string();
+    for row in reports.iter().map(PairingReport::row) {
+        csv.push_str(&format!(
+            "\n{},{},{},{},{},{},{:.3},{:.3},{:.2},{:.2},{}",
+            row.player1, row.player2, row.matches, row.player1_wins, row.player2_wins, row.unfinished,
+            row.player1_win_rate, row.player2_win_rate, row.average_rally_length, row.average_point_duration,
+            row.stalled_points,
+        ));
+    }
+    csv
+}
+
+pub fn to_json(reports: &[PairingReport]) -> String {
+    let rows: Vec<ReportRow> = reports.iter().map(PairingReport::row).collect();
+    serde_json::to_string_pretty(&rows).unwrap_or_else(|error| format!("{{\"error\": {:?}}}", error.to_string()))
+}
+
+#[derive(Resource)]
+struct Batch {
+    report: PairingReport,
+    matches_left: u32,
+    rally_seconds: f32,
+    stalled_this_match: u32,
+}
+
+fn vary_serve(mut rng: ResMut<GameRng>, mut balls: Query<&mut Velocity, With<Ball>>) {
+    let angle = rng.gen_range(-MAX_SERVE_ANGLE..=MAX_SERVE_ANGLE).to_radians();
+
+    for mut velocity in &mut balls {
+        let speed = velocity.linvel.length();
+        let direction = velocity.linvel.x.signum();
+        velocity.linvel = Vec2::new(angle.cos() * direction, angle.sin()) * speed;
+    }
+}
+
+fn start_rally_clock(_trigger: Trigger<ServeStarted>, mut batch: ResMut<Batch>) {
+    batch.rally_seconds = 0.;
+}
+
+fn replay_stalled_rally(
+    time: Res<Time>,
+    mut batch: ResMut<Batch>,
+    mut next_match_state: ResMut<NextState<MatchState>>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+) {
+    batch.rally_seconds += time.delta_secs();
+    if batch.rally_seconds < MAX_RALLY_SECONDS {
+        return;
+    }
+
+    batch.rally_seconds = 0.;
+    batch.report.stalled_points += 1;
+    batch.stalled_this_match += 1;
+
+    if batch.stalled_this_match > MAX_STALLED_POINTS {
+        next_game_state.set(GameState::Endgame);
+    } else {
+        next_match_state.set(MatchState::PointScored);
+    }
+}
+
+fn record_point(_trigger: Trigger<PointScored>, stats: Res<MatchStats>, mut batch: ResMut<Batch>) {
+    batch.report.points += 1;
+    batch.report.rally_hits += stats.rally_hits;
+    batch.report.point_seconds += batch.rally_seconds;
+}
+
+fn record_match(
+    trigger: Trigger<MatchEnded>,
+    rules: Res<Rules>,
+    mut batch: ResMut<Batch>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let (player1, player2) = trigger.score;
+    let report = &mut batch.report;
+
+    report.matches += 1;
+    match trigger.winner.filter(|_| player1.max(player2) >= rules.target_score) {
+        Some(1) => report.wins.0 += 1,
+        Some(_) => report.wins.1 += 1,
+        None => report.unfinished += 1,
+    }
+
+    batch.stalled_this_match = 0;
+    batch.matches_left -= 1;
+    if batch.matches_left > 0 {
+        next_state.set(GameState::Playing);
+    }
+}
+
+/// Plays `options.matches` matches between two computer players.
+pub fn simulate(player1: Difficulty, player2: Difficulty, options: BatchOptions) -> PairingReport {
+    let mut app = App::new();
+    app.add_plugins((
+        HeadlessPlugin::new(),
+        GamePlugin::new().without_storage(),
+        PongPlugin::new()
+            .with_arena_size(options.arena_size.x, options.arena_size.y)
+            .with_rules(Rules { target_score: options.target_score })
+            .with_seed(options.seed),
+    ))
+        .insert_resource(Batch {
+            report: PairingReport { player1, player2, ..default() },
+            matches_left: options.matches,
+            rally_seconds: 0.,
+            stalled_this_match: 0,
+        })
+        .add_observer(start_rally_clock)
+        .add_observer(record_point)
+        .add_observer(record_match)
+        .add_systems(OnEnter(MatchState::Rally), vary_serve)
+        .add_systems(Update, replay_stalled_rally.in_set(RallySet));
+
+    let mut settings = app.world_mut().resource_mut::<GameSettings>();
+    settings.set_mode(GameMode::Versus);
+    settings.update_players(1, PlayerType::Computer(player1));
+    settings.update_players(2, PlayerType::Computer(player2));
+    app.world_mut().resource_mut::<NextState<GameState>>().set(GameState::Playing);
+
+    while app.world().resource::<Batch>().matches_left > 0 {
+        app.update();
+    }
+
+    app.world().resource::<Batch>().report
+}
+
+/// Simulates every difficulty pairing in parallel.
+pub fn calibrate(options: BatchOptions) -> Vec<PairingReport> {
+    let pairings: Vec<(Difficulty, Difficulty)> = Difficulty::ALL.iter()
+        .flat_map(|player1| Difficulty::ALL.iter().map(move |player2| (*player1, *player2)))
+        .collect();
+
+    thread::scope(|scope| {
+        let runs: Vec<_> = pairings.into_iter()
+            .map(|(player1, player2)| scope.spawn(move || simulate(player1, player2, options)))
+            .collect();
+
+        runs.into_iter()
+            .map(|run| run.join().expect("simulation thread panicked"))
+            .collect()
+    })
+}
\ No newline at end of file
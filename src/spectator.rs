@@ -0,0 +1,303 @@
+//! Local spectator feed that broadcasts the match state as JSON over WebSocket.
+//!
+//! Every `decimation` physics ticks each connected client receives one text
+//! message holding a [`StateFrame`], for example:
+//!
+//! ```json
+//! {
+//!   "version": 2,
+//!   "tick": 640,
+//!   "game_state": "Playing",
+//!   "match_state": "Rally",
+//!   "match_point": false,
+//!   "paused": false,
+//!   "score": [2, 1],
+//!   "ball": { "x": 12.5, "y": -40.0, "vx": 310.2, "vy": 88.1 },
+//!   "players": [
+//!     { "num": 1, "name": "Player 1", "kind": "human", "paddle": { "x": -600.0, "y": -50.0, "vx": 0.0, "vy": 384.0 } },
+//!     { "num": 2, "name": "Ada", "kind": "ai:impossible", "paddle": { "x": 600.0, "y": -42.0, "vx": 0.0, "vy": 0.0 } }
+//!   ]
+//! }
+//! ```
+//!
+//! Positions are in world units with the origin at the arena centre and `y`
+//! pointing up; velocities are in units per second. The server never reads
+//! from clients, drops clients that fall too far behind, and accepts at most
+//! [`MAX_SPECTATORS`] connections at a time.
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::{PhysicsSet, Velocity};
+use serde::{Deserialize, Serialize};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use tungstenite::Message;
+
+use crate::game::settings::{GameSettings, PlayerType};
+use crate::game::states::{GameState, MatchPointState, MatchState, PausedState};
+use crate::pong::{Ball, Score, ScoreField};
+
+/// Bumped whenever a field of [`StateFrame`] changes meaning or is removed.
+pub const SCHEMA_VERSION: u32 = 2;
+pub const DEFAULT_PORT: u16 = 9001;
+/// Connections beyond this, including unfinished handshakes, are closed right away.
+pub const MAX_SPECTATORS: usize = 16;
+
+/// Frames waiting for the broadcast thread; newer frames are dropped when it is full.
+const FRAME_QUEUE: usize = 256;
+/// Frames waiting for one client; the client is dropped when it is full.
+const CLIENT_QUEUE: usize = 64;
+/// Longest a handshake or a single write may block a client's thread.
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub struct BodyState {
+    pub x: f32,
+    pub y: f32,
+    pub vx: f32,
+    pub vy: f32,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct PlayerState {
+    /// 1 plays on the left, 2 on the right.
+    pub num: usize,
+    /// Profile name, or "Player <num>" without a profile.
+    pub name: String,
+    /// "human" or "ai:<difficulty>".
+    pub kind: String,
+    /// Missing when the player has no paddle, e.g. player 2 in practice.
+    pub paddle: Option<BodyState>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct StateFrame {
+    pub version: u32,
+    /// Physics ticks since the game started.
+    pub tick: u64,
+    /// "Main", "Playing" or "Endgame".
+    pub game_state: String,
+    /// "Serving", "Rally" or "PointScored" while playing.
+    pub match_state: Option<String>,
+    /// Whether the next point can decide the match.
+    pub match_point: bool,
+    pub paused: bool,
+    pub score: (u32, u32),
+    /// Missing outside of a match.
+    pub ball: Option<BodyState>,
+    pub players: Vec<PlayerState>,
+}
+
+enum Outgoing {
+    Client(SyncSender<String>),
+    Frame(String),
+}
+
+/// Address the spectator server listens on, useful when binding to port 0.
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct SpectatorAddress(pub SocketAddr);
+
+#[derive(Resource)]
+struct SpectatorFeed {
+    sender: SyncSender<Outgoing>,
+    decimation: u64,
+    tick: u64,
+    last_paddles: [Option<Vec2>; 2],
+}
+
+/// Frees a spectator slot when the client's thread ends.
+struct ClientSlot(Arc<AtomicUsize>);
+
+impl Drop for ClientSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+fn accept_clients(listener: TcpListener, sender: SyncSender<Outgoing>) {
+    let connected = Arc::new(AtomicUsize::new(0));
+
+    for stream in listener.incoming().flatten() {
+        if connected.fetch_add(1, Ordering::SeqCst) >= MAX_SPECTATORS {
+            connected.fetch_sub(1, Ordering::SeqCst);
+            debug!("Spectator limit reached, closed connection");
+            continue;
+        }
+
+        let (sender, slot) = (sender.clone(), ClientSlot(connected.clone()));
+        thread::spawn(move || serve_client(stream, sender, slot));
+    }
+}
+
+/// Runs on its own thread, so a client that stalls only ever blocks itself.
+fn serve_client(stream: TcpStream, sender: SyncSender<Outgoing>, _slot: ClientSlot) {
+    if stream.set_read_timeout(Some(CLIENT_TIMEOUT)).is_err() || stream.set_write_timeout(Some(CLIENT_TIMEOUT)).is_err() {
+        return;
+    }
+    let Ok(mut socket) = tungstenite::accept(stream) else {
+        return;
+    };
+
+    let (frames, receiver) = mpsc::sync_channel(CLIENT_QUEUE);
+    if sender.send(Outgoing::Client(frames)).is_err() {
+        return;
+    }
+    drop(sender);
+
+    for text in receiver {
+        if socket.send(Message::text(text)).is_err() {
+            return;
+        }
+    }
+}
+
+fn broadcast(receiver: Receiver<Outgoing>) {
+    let mut clients: Vec<SyncSender<String>> = Vec::new();
+
+    for outgoing in receiver {
+        match outgoing {
+            Outgoing::Client(client) => clients.push(client),
+            Outgoing::Frame(text) => {
+                // A full queue means the client is not keeping up, so let it go
+                clients.retain(|client| client.try_send(text.clone()).is_ok());
+            },
+        }
+    }
+}
+
+fn player_state(settings: &GameSettings, player_num: usize, paddle: Option<BodyState>) -> PlayerState {
+    let player_type: &PlayerType = if player_num == 1 { settings.get_player1() } else { settings.get_player2() };
+
+    PlayerState {
+        num: player_num,
+        name: settings.get_profile(player_num)
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("Player {}", player_num)),
+        kind: player_type.to_string(),
+        paddle,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn send_state(
+    mut feed: ResMut<SpectatorFeed>,
+    time: Res<Time>,
+    game_state: Res<State<GameState>>,
+    match_state: Option<Res<State<MatchState>>>,
+    match_point: Option<Res<State<MatchPointState>>>,
+    paused_state: Res<State<PausedState>>,
+    score: Res<Score>,
+    settings: Res<GameSettings>,
+    balls: Query<(&Transform, &Velocity), With<Ball>>,
+    paddles: Query<(&Transform, &ScoreField), With<PlayerType>>,
+) {
+    let tick = feed.tick;
+    feed.tick += 1;
+
+    // Paddles are moved kinematically, so derive their velocity from the last tick
+    let mut paddle_states = [None, None];
+    for (transform, field) in &paddles {
+        let index = if *field == ScoreField::Left { 0 } else { 1 };
+        let position = transform.translation.truncate();
+        let velocity = feed.last_paddles[index]
+            .map(|last| (position - last) / time.delta_secs().max(f32::EPSILON))
+            .unwrap_or_default();
+
+        feed.last_paddles[index] = Some(position);
+        paddle_states[index] = Some(BodyState { x: position.x, y: position.y, vx: velocity.x, vy: velocity.y });
+    }
+    if paddles.is_empty() {
+        feed.last_paddles = [None, None];
+    }
+
+    if !tick.is_multiple_of(feed.decimation) {
+        return;
+    }
+
+    let frame = StateFrame {
+        version: SCHEMA_VERSION,
+        tick,
+        game_state: format!("{:?}", game_state.get()),
+        match_state: match_state.map(|state| format!("{:?}", state.get())),
+        match_point: match_point.is_some_and(|state| *state.get() == MatchPointState::Active),
+        paused: *paused_state.get() == PausedState::Paused,
+        score: score.points(),
+        ball: balls.iter().next().map(|(transform, velocity)| BodyState {
+            x: transform.translation.x,
+            y: transform.translation.y,
+            vx: velocity.linvel.x,
+            vy: velocity.linvel.y,
+        }),
+        players: vec![
+            player_state(&settings, 1, paddle_states[0]),
+            player_state(&settings, 2, paddle_states[1]),
+        ],
+    };
+
+    if let Ok(text) = serde_json::to_string(&frame) {
+        if let Err(TrySendError::Full(_)) = feed.sender.try_send(Outgoing::Frame(text)) {
+            debug!("Spectator broadcast is behind, dropped frame {}", tick);
+        }
+    }
+}
+
+pub struct SpectatorPlugin {
+    address: SocketAddr,
+    decimation: u32,
+}
+
+impl Default for SpectatorPlugin {
+    fn default() -> Self {
+        Self {
+            address: SocketAddr::from(([127, 0, 0, 1], DEFAULT_PORT)),
+            decimation: 1,
+        }
+    }
+}
+
+impl SpectatorPlugin {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_address(mut self, address: SocketAddr) -> Self {
+        self.address = address;
+        self
+    }
+
+    /// Sends a frame every `decimation` physics ticks instead of on every tick.
+    pub fn with_decimation(mut self, decimation: u32) -> Self {
+        self.decimation = decimation.max(1);
+        self
+    }
+}
+
+impl Plugin for SpectatorPlugin {
+    fn build(&self, app: &mut App) {
+        let listener = match TcpListener::bind(self.address) {
+            Ok(listener) => listener,
+            Err(error) => {
+                warn!("Could not start spectator server on {}: {}", self.address, error);
+                return;
+            },
+        };
+        let address = listener.local_addr().unwrap_or(self.address);
+
+        let (sender, receiver) = mpsc::sync_channel(FRAME_QUEUE);
+        let acceptor = sender.clone();
+        thread::spawn(move || accept_clients(listener, acceptor));
+        thread::spawn(move || broadcast(receiver));
+
+        app.insert_resource(SpectatorAddress(address))
+            .insert_resource(SpectatorFeed {
+                sender,
+                decimation: self.decimation as u64,
+                tick: 0,
+                last_paddles: [None, None],
+            })
+            .add_systems(FixedPostUpdate, send_state.after(PhysicsSet::Writeback));
+    }
+}
\ No newline at end of file
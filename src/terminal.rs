@@ -0,0 +1,349 @@
+//! Text-mode frontend that draws the match with characters and reads keys from the TTY.
+
+use bevy::input::keyboard::{Key, KeyboardInput, NativeKey};
+use bevy::input::{ButtonState, InputSystem};
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy_rapier2d::prelude::{Collider, Sensor};
+use crossterm::event::{self, Event, KeyEventKind, KeyModifiers, KeyboardEnhancementFlags};
+use crossterm::{cursor, execute, queue, style, terminal};
+use std::io::{self, IsTerminal, Write};
+use std::time::Duration;
+
+use crate::cli::ArenaPreset;
+use crate::game::settings::{GameSettings, PlayerType};
+use crate::game::states::{GameState, MatchState, PausedState};
+use crate::pong::{Arena, Ball, CountdownDisplay, PracticeDisplay, Rules, ScoreDisplay, Score};
+use crate::locale::{LocalizationPlugin, Translations};
+
+/// Terminals without key release events only repeat held keys, after a delay.
+const HOLD_SECONDS: f32 = 0.5;
+/// How often frames are printed when output is not a terminal, e.g. in CI logs.
+const PLAIN_FRAME_SECONDS: f32 = 1.;
+const PLAIN_SIZE: (u16, u16) = (80, 24);
+const STATUS_ROWS: u16 = 2;
+
+/// Whether the game can be played from this terminal.
+pub fn is_interactive() -> bool {
+    io::stdin().is_terminal() && io::stdout().is_terminal()
+}
+
+/// Maps a terminal key to the Bevy key the game binds, if it has one.
+pub fn key_code(code: event::KeyCode) -> Option<KeyCode> {
+    let key = match code {
+        event::KeyCode::Up => KeyCode::ArrowUp,
+        event::KeyCode::Down => KeyCode::ArrowDown,
+        event::KeyCode::Left => KeyCode::ArrowLeft,
+        event::KeyCode::Right => KeyCode::ArrowRight,
+        event::KeyCode::Esc => KeyCode::Escape,
+        event::KeyCode::Enter => KeyCode::Enter,
+        event::KeyCode::Char(' ') => KeyCode::Space,
+        event::KeyCode::Char(character) => match character.to_ascii_lowercase() {
+            'a' => KeyCode::KeyA, 'b' => KeyCode::KeyB, 'c' => KeyCode::KeyC, 'd' => KeyCode::KeyD,
+            'e' => KeyCode::KeyE, 'f' => KeyCode::KeyF, 'g' => KeyCode::KeyG, 'h' => KeyCode::KeyH,
+            'i' => KeyCode::KeyI, 'j' => KeyCode::KeyJ, 'k' => KeyCode::KeyK, 'l' => KeyCode::KeyL,
+            'm' => KeyCode::KeyM, 'n' => KeyCode::KeyN, 'o' => KeyCode::KeyO, 'p' => KeyCode::KeyP,
+            'q' => KeyCode::KeyQ, 'r' => KeyCode::KeyR, 's' => KeyCode::KeyS, 't' => KeyCode::KeyT,
+            'u' => KeyCode::KeyU, 'v' => KeyCode::KeyV, 'w' => KeyCode::KeyW, 'x' => KeyCode::KeyX,
+            'y' => KeyCode::KeyY, 'z' => KeyCode::KeyZ,
+            _ => return None,
+        },
+        _ => return None,
+    };
+    Some(key)
+}
+
+#[derive(Resource)]
+pub struct TerminalScreen {
+    interactive: bool,
+    release_events: bool,
+    held: HashMap<KeyCode, f32>,
+    since_frame: f32,
+}
+
+impl TerminalScreen {
+    fn open() -> Self {
+        let mut screen = Self {
+            interactive: false,
+            release_events: false,
+            held: HashMap::default(),
+            since_frame: PLAIN_FRAME_SECONDS,
+        };
+        if !is_interactive() || terminal::enable_raw_mode().is_err() {
+            return screen;
+        }
+
+        screen.interactive = true;
+        screen.release_events = terminal::supports_keyboard_enhancement().unwrap_or(false);
+
+        let mut stdout = io::stdout();
+        let _ = execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide);
+        if screen.release_events {
+            let _ = execute!(stdout, event::PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::REPORT_EVENT_TYPES));
+        }
+
+        screen
+    }
+
+    fn size(&self) -> (u16, u16) {
+        if self.interactive { terminal::size().unwrap_or(PLAIN_SIZE) }
+        else { PLAIN_SIZE }
+    }
+}
+
+impl Drop for TerminalScreen {
+    fn drop(&mut self) {
+        if !self.interactive {
+            return;
+        }
+
+        let mut stdout = io::stdout();
+        if self.release_events {
+            let _ = execute!(stdout, event::PopKeyboardEnhancementFlags);
+        }
+        let _ = execute!(stdout, cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+fn send_key(keyboard: &mut EventWriter<KeyboardInput>, key_code: KeyCode, state: ButtonState) {
+    keyboard.send(KeyboardInput {
+        key_code,
+        logical_key: Key::Unidentified(NativeKey::Unidentified),
+        state,
+        repeat: false,
+        window: Entity::PLACEHOLDER,
+    });
+}
+
+#[allow(clippy::too_many_arguments)]
+fn read_input(
+    time: Res<Time<Real>>,
+    mut screen: ResMut<TerminalScreen>,
+    mut keyboard: EventWriter<KeyboardInput>,
+    mut exit: EventWriter<AppExit>,
+    game_state: Res<State<GameState>>,
+    paused_state: Res<State<PausedState>>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    mut next_paused_state: ResMut<NextState<PausedState>>,
+) {
+    if !screen.interactive {
+        return;
+    }
+
+    let now = time.elapsed_secs();
+    while event::poll(Duration::ZERO).unwrap_or(false) {
+        let Ok(Event::Key(key)) = event::read() else {
+            continue;
+        };
+
+        match (key.code, key.kind) {
+            (event::KeyCode::Char('c'), KeyEventKind::Press) if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                exit.send(AppExit::Success);
+            },
+            (event::KeyCode::Char('q'), KeyEventKind::Press) => {
+                exit.send(AppExit::Success);
+            },
+            (event::KeyCode::Char('p'), KeyEventKind::Press) if *game_state.get() == GameState::Playing => {
+                next_paused_state.set(match paused_state.get() {
+                    PausedState::Playing => PausedState::Paused,
+                    PausedState::Paused => PausedState::Playing,
+                });
+            },
+            (event::KeyCode::Char('r'), KeyEventKind::Press) if *game_state.get() == GameState::Endgame => {
+                next_game_state.set(GameState::Playing);
+            },
+            (code, KeyEventKind::Release) => {
+                if let Some(key_code) = key_code(code).filter(|key_code| screen.held.remove(key_code).is_some()) {
+                    send_key(&mut keyboard, key_code, ButtonState::Released);
+                }
+            },
+            (code, _) => {
+                let Some(key_code) = key_code(code) else {
+                    continue;
+                };
+                if screen.held.insert(key_code, now).is_none() {
+                    send_key(&mut keyboard, key_code, ButtonState::Pressed);
+                }
+            },
+        }
+    }
+
+    // Without release events a key counts as held until its repeats stop
+    if !screen.release_events {
+        let released: Vec<KeyCode> = screen.held.iter()
+            .filter(|(_, seen)| now - **seen > HOLD_SECONDS)
+            .map(|(key_code, _)| *key_code)
+            .collect();
+
+        for key_code in released {
+            screen.held.remove(&key_code);
+            send_key(&mut keyboard, key_code, ButtonState::Released);
+        }
+    }
+}
+
+/// Character cells covering the arena, top row first.
+pub struct Grid {
+    cols: usize,
+    rows: usize,
+    arena: Vec2,
+    cells: Vec<char>,
+}
+
+impl Grid {
+    pub fn new(cols: u16, rows: u16, arena: Vec2) -> Self {
+        let (cols, rows) = (cols.max(1) as usize, rows.max(1) as usize);
+        Self { cols, rows, arena, cells: vec![' '; cols * rows] }
+    }
+
+    /// Column and row of the cell containing `position`, which may lie outside the grid.
+    pub fn cell(&self, position: Vec2) -> (isize, isize) {
+        let col = (position.x + self.arena.x / 2.) / self.arena.x * self.cols as f32;
+        let row = (self.arena.y / 2. - position.y) / self.arena.y * self.rows as f32;
+        (col.floor() as isize, row.floor() as isize)
+    }
+
+    /// Fills every cell the rectangle touches, clipped to the grid.
+    pub fn fill(&mut self, center: Vec2, half_extents: Vec2, character: char) {
+        let (left, top) = self.cell(center + Vec2::new(-half_extents.x, half_extents.y));
+        let (right, bottom) = self.cell(center + Vec2::new(half_extents.x, -half_extents.y));
+
+        for row in top.max(0)..=bottom.min(self.rows as isize - 1) {
+            for col in left.max(0)..=right.min(self.cols as isize - 1) {
+                self.cells[row as usize * self.cols + col as usize] = character;
+            }
+        }
+    }
+
+    /// Writes `text` centred on `row`, cut to the grid width.
+    pub fn write(&mut self, row: usize, text: &str) {
+        let len = text.chars().count().min(self.cols);
+        let start = row * self.cols + (self.cols - len) / 2;
+        for (index, character) in text.chars().take(len).enumerate() {
+            self.cells[start + index] = character;
+        }
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = String> + '_ {
+        self.cells.chunks(self.cols).map(|row| row.iter().collect())
+    }
+}
+
+fn player_name(settings: &GameSettings, player_num: usize, tr: &Translations) -> String {
+    settings.get_profile(player_num)
+        .map(str::to_string)
+        .unwrap_or_else(|| tr.format("player.name", &[("num", &player_num.to_string())]))
+}
+
+fn player_label(settings: &GameSettings, player_num: usize, tr: &Translations) -> String {
+    let player_type = if player_num == 1 { settings.get_player1() } else { settings.get_player2() };
+    let name = player_name(settings, player_num, tr);
+
+    match player_type {
+        PlayerType::Human => name,
+        PlayerType::Computer(difficulty) => format!("{} ({})", name, tr.text(&format!("player.{}", difficulty))),
+    }
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn render(
+    time: Res<Time>,
+    mut screen: ResMut<TerminalScreen>,
+    arena: Res<Arena>,
+    score: Res<Score>,
+    rules: Res<Rules>,
+    settings: Res<GameSettings>,
+    tr: Res<Translations>,
+    game_state: Res<State<GameState>>,
+    match_state: Option<Res<State<MatchState>>>,
+    paused_state: Res<State<PausedState>>,
+    shapes: Query<(&GlobalTransform, &Collider, Has<Ball>, Has<PlayerType>), Without<Sensor>>,
+    score_text: Query<&Text2d, With<ScoreDisplay>>,
+    countdown_text: Query<&Text2d, With<CountdownDisplay>>,
+    practice_text: Query<&Text2d, With<PracticeDisplay>>,
+) {
+    if !screen.interactive {
+        screen.since_frame += time.delta_secs();
+        if screen.since_frame < PLAIN_FRAME_SECONDS && !game_state.is_changed() {
+            return;
+        }
+        screen.since_frame = 0.;
+    }
+
+    let (cols, rows) = screen.size();
+    let mut grid = Grid::new(cols, rows.saturating_sub(STATUS_ROWS), arena.size.unwrap_or(ArenaPreset::Classic.size()));
+
+    for (transform, collider, is_ball, is_paddle) in &shapes {
+        let center = transform.translation().truncate();
+        if is_ball {
+            grid.fill(center, Vec2::ZERO, 'O');
+        } else if let Some(cuboid) = collider.as_cuboid() {
+            grid.fill(center, cuboid.half_extents(), if is_paddle { '#' } else { '=' });
+        }
+    }
+
+    let overlay = countdown_text.iter().map(|text| text.0.clone()).find(|text| !text.is_empty())
+        .or_else(|| (*paused_state.get() == PausedState::Paused).then(|| tr.text("paused.title")))
+        .or_else(|| score.get_winner_num()
+            .filter(|_| *game_state.get() == GameState::Endgame && score.is_game_end(&rules))
+            .map(|winner| tr.format("terminal.winner", &[("winner", &player_name(&settings, winner, &tr))])));
+    if let Some(overlay) = overlay {
+        grid.write(grid.rows / 2, &overlay);
+    }
+
+    let score_line = score_text.iter().next().map(|text| text.0.clone()).unwrap_or_else(|| score.display_text());
+    let mut status = format!("{}   {}   {}", player_label(&settings, 1, &tr), score_line, player_label(&settings, 2, &tr));
+    if let Some(practice) = practice_text.iter().next().filter(|text| !text.is_empty()) {
+        status = format!("{}   {}", status, practice.0);
+    }
+    let state = match &match_state {
+        Some(match_state) => format!("{:?}", match_state.get()),
+        None => format!("{:?}", game_state.get()),
+    };
+    let help = format!("{}  |  {}", state, tr.text("terminal.help"));
+
+    let mut stdout = io::stdout();
+    if screen.interactive {
+        let _ = queue!(stdout, cursor::MoveTo(0, 0));
+        for line in [status, help].into_iter().chain(grid.lines()) {
+            let _ = queue!(stdout, terminal::Clear(terminal::ClearType::CurrentLine), style::Print(line), style::Print("\r\n"));
+        }
+    } else {
+        let _ = writeln!(stdout, "{}\n{}", status, help);
+        for line in grid.lines() {
+            let _ = writeln!(stdout, "{}", line.trim_end());
+        }
+        let _ = writeln!(stdout, "{}", "-".repeat(grid.cols));
+    }
+    let _ = stdout.flush();
+}
+
+fn open_screen(mut commands: Commands) {
+    commands.insert_resource(TerminalScreen::open());
+}
+
+/// Without a terminal to play in, stop once the match is over.
+fn exit_after_match(screen: Res<TerminalScreen>, mut exit: EventWriter<AppExit>) {
+    if !screen.interactive {
+        exit.send(AppExit::Success);
+    }
+}
+
+/// Draws the match in the terminal on top of [`crate::HeadlessPlugin`].
+pub struct TerminalPlugin;
+
+impl Plugin for TerminalPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<LocalizationPlugin>() {
+            app.add_plugins(LocalizationPlugin);
+        }
+
+        app.add_systems(Startup, open_screen)
+            .add_systems(PreUpdate, read_input.before(InputSystem))
+            .add_systems(Last, (
+                render,
+                exit_after_match.run_if(in_state(GameState::Endgame)),
+            ).chain());
+    }
+}
\ No newline at end of file
@@ -1,4 +1,4 @@
-mod menu;
+pub mod menu;
 mod systems;
 
 pub use systems::MenuSystemsPlugin;
\ No newline at end of file
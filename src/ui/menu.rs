@@ -1,6 +1,9 @@
 pub mod components;
 pub mod actions;
 pub mod builder;
+pub mod definition;
+pub mod registry;
+pub mod navigation;
 
 pub mod style {
     use bevy_egui::egui::Color32;
@@ -15,4 +18,5 @@ pub mod style {
     pub const BACKGROUND_COLOR: Color32 = Color32::from_rgb(70, 70, 70);
     pub const HOVER_COLOR: Color32 = Color32::from_rgb(120, 120, 120);
     pub const ACTIVE_COLOR: Color32 = Color32::from_rgb(50, 50, 50);
+    pub const BACKDROP_COLOR: Color32 = Color32::from_black_alpha(180);
 }
\ No newline at end of file
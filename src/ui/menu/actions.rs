@@ -1,10 +1,18 @@
 use bevy::prelude::*;
 use bevy::state::state::FreelyMutableState;
+use std::marker::PhantomData;
+use std::sync::Arc;
 
-pub trait MenuAction {
+pub trait MenuAction: Send + Sync {
     fn execute(&self, commands: &mut Commands);
 }
 
+impl MenuAction for Box<dyn MenuAction> {
+    fn execute(&self, commands: &mut Commands) {
+        self.as_ref().execute(commands);
+    }
+}
+
 pub struct ChangeStateMenuAction<State: FreelyMutableState> {
     next_state: State,
 }
@@ -45,34 +53,140 @@ impl MenuAction for QuitMenuAction {
     }
 }
 
-pub struct ClosureMenuAction<F> where F: Fn(&mut Commands) {
+pub struct ClosureMenuAction<F> where F: Fn(&mut Commands) + Send + Sync {
     closure: F,
 }
 
-impl<F> ClosureMenuAction<F> where F: Fn(&mut Commands) {
+impl<F> ClosureMenuAction<F> where F: Fn(&mut Commands) + Send + Sync {
     pub fn new(closure: F) -> Self {
         Self { closure }
     }
 }
 
-impl<F> MenuAction for ClosureMenuAction<F> where F: Fn(&mut Commands) {
+impl<F> MenuAction for ClosureMenuAction<F> where F: Fn(&mut Commands) + Send + Sync {
     fn execute(&self, commands: &mut Commands) {
         (self.closure)(commands);
     }
 }
 
-pub struct CommandMenuAction<C> where C: Command+Clone {
+pub struct CommandMenuAction<C> where C: Command+Clone+Sync {
     command: C,
 }
 
-impl<C> CommandMenuAction<C> where C: Command+Clone {
+impl<C> CommandMenuAction<C> where C: Command+Clone+Sync {
     pub fn new(command: C) -> Self {
         Self { command }
     }
 }
 
-impl<C> MenuAction for CommandMenuAction<C> where C: Command+Clone {
+impl<C> MenuAction for CommandMenuAction<C> where C: Command+Clone+Sync {
     fn execute(&self, commands: &mut Commands) {
         commands.queue(self.command.clone());
     }
-}
\ No newline at end of file
+}
+
+#[derive(Resource, Clone)]
+pub struct MenuConfirmation {
+    pub message: String,
+    pub action: Arc<dyn MenuAction>,
+}
+
+pub struct ConfirmMenuAction {
+    confirmation: MenuConfirmation,
+}
+
+impl ConfirmMenuAction {
+    pub fn new(message: impl Into<String>, action: impl MenuAction + 'static) -> Self {
+        Self {
+            confirmation: MenuConfirmation {
+                message: message.into(),
+                action: Arc::new(action),
+            },
+        }
+    }
+}
+
+impl MenuAction for ConfirmMenuAction {
+    fn execute(&self, commands: &mut Commands) {
+        commands.insert_resource(self.confirmation.clone());
+    }
+}
+
+pub trait MenuValueAction<T> {
+    fn execute(&self, value: T, commands: &mut Commands);
+}
+
+impl<T> MenuValueAction<T> for Box<dyn MenuValueAction<T>> {
+    fn execute(&self, value: T, commands: &mut Commands) {
+        self.as_ref().execute(value, commands);
+    }
+}
+
+/// A value read from or written to the world by a data-driven menu widget.
+#[derive(Clone, PartialEq, Debug)]
+pub enum MenuValue {
+    Number(f32),
+    Bool(bool),
+    Text(String),
+}
+
+impl MenuValue {
+    pub fn as_number(&self) -> Option<f32> {
+        match self {
+            MenuValue::Number(number) => Some(*number),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            MenuValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            MenuValue::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+}
+
+pub struct ClosureMenuValueAction<F, T> where F: Fn(T, &mut Commands) {
+    closure: F,
+    _value: PhantomData<T>,
+}
+
+impl<F, T> ClosureMenuValueAction<F, T> where F: Fn(T, &mut Commands) {
+    pub fn new(closure: F) -> Self {
+        Self { closure, _value: PhantomData }
+    }
+}
+
+impl<F, T> MenuValueAction<T> for ClosureMenuValueAction<F, T> where F: Fn(T, &mut Commands) {
+    fn execute(&self, value: T, commands: &mut Commands) {
+        (self.closure)(value, commands);
+    }
+}
+
+pub struct UpdateResourceValueMenuAction<R: Resource, T> {
+    update: fn(&mut R, T),
+}
+
+impl<R: Resource, T> UpdateResourceValueMenuAction<R, T> {
+    pub fn new(update: fn(&mut R, T)) -> Self {
+        Self { update }
+    }
+}
+
+impl<R: Resource, T: Send + 'static> MenuValueAction<T> for UpdateResourceValueMenuAction<R, T> {
+    fn execute(&self, value: T, commands: &mut Commands) {
+        let update = self.update;
+        commands.queue(move |world: &mut World| {
+            if let Some(mut resource) = world.get_resource_mut::<R>() {
+                update(&mut resource, value);
+            }
+        });
+    }
+}
@@ -1,7 +1,10 @@
 use bevy::prelude::*;
 use bevy_egui::{egui, EguiContexts};
 use crate::ui::menu::components::MenuComponent;
+use crate::ui::menu::definition::MenuDefinition;
+use crate::ui::menu::registry::MenuRegistry;
 use crate::ui::menu::style;
+use crate::locale::Translations;
 
 pub struct MenuBuilder {
     heading: String,
@@ -18,6 +21,23 @@ impl MenuBuilder {
         }
     }
 
+    pub fn from_definition(
+        definition: &MenuDefinition,
+        registry: &MenuRegistry,
+        translations: &Translations,
+        world: &World,
+    ) -> Self {
+        let mut builder = Self::new(definition.heading.resolve(registry, translations, world));
+        if let Some(top_spacing) = definition.top_spacing {
+            builder = builder.with_top_spacing(top_spacing);
+        }
+
+        builder.components.extend(definition.components.iter()
+            .filter_map(|component| component.instantiate(registry, translations, world)));
+
+        builder
+    }
+
     pub fn with_top_spacing(mut self, top_spacing: f32) -> Self {
         self.top_spacing = top_spacing;
         self
@@ -64,8 +84,8 @@ impl MenuBuilder {
 
     fn create_widget_style(&self, color: egui::Color32) -> egui::style::WidgetVisuals {
         egui::style::WidgetVisuals {
-            bg_fill: color.clone(),
-            weak_bg_fill: color.clone(),
+            bg_fill: color,
+            weak_bg_fill: color,
             fg_stroke: egui::Stroke::NONE,
             bg_stroke: egui::Stroke::NONE,
             rounding: egui::Rounding::default(),
@@ -1,12 +1,19 @@
 use bevy::prelude::*;
 use bevy_egui::egui;
-use crate::ui::menu::actions::MenuAction;
+use std::ops::RangeInclusive;
+use crate::ui::menu::actions::{MenuAction, MenuConfirmation, MenuValueAction};
 use crate::ui::menu::style;
 
 pub trait MenuComponent {
     fn build(&mut self, ui: &mut egui::Ui, commands: &mut Commands);
 }
 
+impl MenuComponent for Box<dyn MenuComponent> {
+    fn build(&mut self, ui: &mut egui::Ui, commands: &mut Commands) {
+        self.as_mut().build(ui, commands);
+    }
+}
+
 pub struct MenuLabel {
     label: String,
 }
@@ -93,6 +100,7 @@ impl MenuComponent for MenuButton {
     }
 }
 
+#[derive(Default)]
 pub struct MenuLayoutHorizontal {
     components: Vec<Box<dyn MenuComponent>>,
 }
@@ -122,4 +130,298 @@ impl MenuComponent for MenuLayoutHorizontal {
                 });
             });
     }
+}
+
+pub struct MenuLayoutVertical {
+    max_height: f32,
+    components: Vec<Box<dyn MenuComponent>>,
+}
+
+impl MenuLayoutVertical {
+    pub fn new(max_height: f32) -> Self {
+        Self { max_height, components: Vec::new() }
+    }
+
+    pub fn add_component(mut self, component: impl MenuComponent + 'static) -> Self {
+        self.components.push(Box::new(component));
+        self
+    }
+}
+
+impl MenuComponent for MenuLayoutVertical {
+    fn build(&mut self, ui: &mut egui::Ui, commands: &mut Commands) {
+        egui::ScrollArea::vertical()
+            .max_height(self.max_height)
+            .show(ui, |ui| {
+                ui.vertical_centered(|ui| {
+                    for component in &mut self.components {
+                        component.build(ui, commands);
+                        ui.add_space(style::SPACING);
+                    }
+                });
+            });
+    }
+}
+
+pub struct MenuSlider<N: egui::emath::Numeric> {
+    label: String,
+    value: N,
+    range: RangeInclusive<N>,
+    step: Option<f64>,
+    action: Box<dyn MenuValueAction<N>>,
+}
+
+impl<N: egui::emath::Numeric> MenuSlider<N> {
+    pub fn new(
+        label: impl Into<String>,
+        value: N,
+        range: RangeInclusive<N>,
+        action: impl MenuValueAction<N> + 'static,
+    ) -> Self {
+        Self {
+            label: label.into(),
+            value,
+            range,
+            step: None,
+            action: Box::new(action),
+        }
+    }
+
+    pub fn with_step(mut self, step: f64) -> Self {
+        self.step = Some(step);
+        self
+    }
+}
+
+impl<N: egui::emath::Numeric> MenuComponent for MenuSlider<N> {
+    fn build(&mut self, ui: &mut egui::Ui, commands: &mut Commands) {
+        let mut slider = egui::Slider::new(&mut self.value, self.range.clone())
+            .text(egui::RichText::new(&self.label).size(style::TEXT_SIZE).color(style::PRIMARY_COLOR));
+        if let Some(step) = self.step {
+            slider = slider.step_by(step);
+        }
+
+        if ui.add_sized(
+            egui::Vec2::new(style::BUTTON_WIDTH * 2., style::BUTTON_HEIGHT),
+            slider,
+        ).changed() {
+            self.action.execute(self.value, commands);
+        }
+    }
+}
+
+pub struct MenuCheckbox {
+    label: String,
+    checked: bool,
+    action: Box<dyn MenuValueAction<bool>>,
+}
+
+impl MenuCheckbox {
+    pub fn new(
+        label: impl Into<String>,
+        checked: bool,
+        action: impl MenuValueAction<bool> + 'static,
+    ) -> Self {
+        Self {
+            label: label.into(),
+            checked,
+            action: Box::new(action),
+        }
+    }
+}
+
+impl MenuComponent for MenuCheckbox {
+    fn build(&mut self, ui: &mut egui::Ui, commands: &mut Commands) {
+        if ui.add_sized(
+            egui::Vec2::new(style::BUTTON_WIDTH, style::BUTTON_HEIGHT),
+            egui::Checkbox::new(
+                &mut self.checked,
+                egui::RichText::new(&self.label).size(style::TEXT_SIZE).color(style::PRIMARY_COLOR),
+            ),
+        ).changed() {
+            self.action.execute(self.checked, commands);
+        }
+    }
+}
+
+pub struct MenuDropdown<T: PartialEq + Clone> {
+    label: String,
+    selected: T,
+    options: Vec<(String, T)>,
+    action: Box<dyn MenuValueAction<T>>,
+}
+
+impl<T: PartialEq + Clone> MenuDropdown<T> {
+    pub fn new(
+        label: impl Into<String>,
+        selected: T,
+        action: impl MenuValueAction<T> + 'static,
+    ) -> Self {
+        Self {
+            label: label.into(),
+            selected,
+            options: Vec::new(),
+            action: Box::new(action),
+        }
+    }
+
+    pub fn add_option(mut self, label: impl Into<String>, value: T) -> Self {
+        self.options.push((label.into(), value));
+        self
+    }
+}
+
+impl<T: PartialEq + Clone> MenuComponent for MenuDropdown<T> {
+    fn build(&mut self, ui: &mut egui::Ui, commands: &mut Commands) {
+        let selected_text = self.options.iter()
+            .find(|(_, value)| *value == self.selected)
+            .map(|(label, _)| label.clone())
+            .unwrap_or_default();
+        // Labels are not unique, so key the popup state on the widget's position instead.
+        let id_salt = ui.next_auto_id();
+
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(&self.label).size(style::TEXT_SIZE).color(style::PRIMARY_COLOR));
+            egui::ComboBox::from_id_salt(id_salt)
+                .width(style::BUTTON_WIDTH)
+                .selected_text(egui::RichText::new(selected_text).size(style::TEXT_SIZE).color(style::PRIMARY_COLOR))
+                .show_ui(ui, |ui| {
+                    for (label, value) in &self.options {
+                        if ui.selectable_label(
+                            *value == self.selected,
+                            egui::RichText::new(label).size(style::TEXT_SIZE).color(style::PRIMARY_COLOR),
+                        ).clicked() {
+                            self.action.execute(value.clone(), commands);
+                        }
+                    }
+                });
+        });
+    }
+}
+
+pub struct MenuTextInput {
+    label: String,
+    text: String,
+    max_length: usize,
+    action: Box<dyn MenuValueAction<String>>,
+}
+
+impl MenuTextInput {
+    pub fn new(
+        label: impl Into<String>,
+        text: impl Into<String>,
+        action: impl MenuValueAction<String> + 'static,
+    ) -> Self {
+        Self {
+            label: label.into(),
+            text: text.into(),
+            max_length: 32,
+            action: Box::new(action),
+        }
+    }
+
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = max_length;
+        self
+    }
+}
+
+impl MenuComponent for MenuTextInput {
+    fn build(&mut self, ui: &mut egui::Ui, commands: &mut Commands) {
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(&self.label).size(style::TEXT_SIZE).color(style::PRIMARY_COLOR));
+            if ui.add(
+                egui::TextEdit::singleline(&mut self.text)
+                    .char_limit(self.max_length)
+                    .desired_width(style::BUTTON_WIDTH)
+                    .font(egui::FontId::proportional(style::TEXT_SIZE))
+            ).changed() {
+                self.action.execute(self.text.clone(), commands);
+            }
+        });
+    }
+}
+
+pub struct MenuConfirmDialog {
+    confirmation: MenuConfirmation,
+    confirm_label: String,
+    cancel_label: String,
+}
+
+impl MenuConfirmDialog {
+    pub fn new(confirmation: MenuConfirmation) -> Self {
+        Self {
+            confirmation,
+            confirm_label: "Yes".into(),
+            cancel_label: "No".into(),
+        }
+    }
+
+    pub fn with_labels(mut self, confirm_label: impl Into<String>, cancel_label: impl Into<String>) -> Self {
+        self.confirm_label = confirm_label.into();
+        self.cancel_label = cancel_label.into();
+        self
+    }
+}
+
+impl MenuConfirmDialog {
+    /// Shows the dialog in its own layers, so it needs no parent `Ui`.
+    pub fn show(&mut self, ctx: &egui::Context, commands: &mut Commands) {
+        let screen = ctx.screen_rect();
+
+        egui::Area::new(egui::Id::new("menu_confirm_backdrop"))
+            .order(egui::Order::Middle)
+            .fixed_pos(screen.min)
+            .show(ctx, |ui| {
+                ui.painter().rect_filled(screen, 0., style::BACKDROP_COLOR);
+                ui.allocate_rect(screen, egui::Sense::click());
+            });
+
+        egui::Area::new(egui::Id::new("menu_confirm_dialog"))
+            .order(egui::Order::Foreground)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style())
+                    .fill(style::BACKGROUND_COLOR)
+                    .inner_margin(style::SPACING * 2.)
+                    .show(ui, |ui| {
+                        ui.vertical_centered(|ui| {
+                            ui.label(
+                                egui::RichText::new(&self.confirmation.message)
+                                    .size(style::TEXT_SIZE)
+                                    .color(style::PRIMARY_COLOR),
+                            );
+                            ui.add_space(style::SPACING);
+
+                            ui.horizontal(|ui| {
+                                if dialog_button(ui, &self.confirm_label).clicked() {
+                                    self.confirmation.action.execute(commands);
+                                    commands.remove_resource::<MenuConfirmation>();
+                                }
+                                if dialog_button(ui, &self.cancel_label).clicked() {
+                                    commands.remove_resource::<MenuConfirmation>();
+                                }
+                            });
+                        });
+                    });
+            });
+    }
+}
+
+impl MenuComponent for MenuConfirmDialog {
+    fn build(&mut self, ui: &mut egui::Ui, commands: &mut Commands) {
+        let ctx = ui.ctx().clone();
+        self.show(&ctx, commands);
+    }
+}
+
+fn dialog_button(ui: &mut egui::Ui, label: &str) -> egui::Response {
+    ui.add_sized(
+        egui::Vec2::new(style::BUTTON_WIDTH, style::BUTTON_HEIGHT),
+        egui::Button::new(
+            egui::RichText::new(label)
+                .size(style::TEXT_SIZE)
+                .color(style::PRIMARY_COLOR)
+        ),
+    )
 }
\ No newline at end of file
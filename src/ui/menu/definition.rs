@@ -0,0 +1,307 @@
+use bevy::prelude::*;
+use bevy::asset::{io::Reader, AssetLoader, LoadContext};
+use bevy::ecs::system::SystemState;
+use bevy_egui::EguiContexts;
+use serde::Deserialize;
+use std::fmt;
+use crate::ui::menu::actions::{ClosureMenuValueAction, MenuValue, MenuValueAction};
+use crate::ui::menu::builder::MenuBuilder;
+use crate::ui::menu::components::{
+    MenuButton, MenuCheckbox, MenuComponent, MenuDropdown, MenuLabel, MenuLayoutHorizontal, MenuLayoutVertical,
+    MenuSelectableLabel, MenuSlider, MenuTextInput,
+};
+use crate::ui::menu::registry::MenuRegistry;
+use crate::locale::Translations;
+
+#[derive(Asset, TypePath, Deserialize, Debug)]
+pub struct MenuDefinition {
+    pub heading: TextDefinition,
+    #[serde(default)]
+    pub top_spacing: Option<f32>,
+    pub components: Vec<ComponentDefinition>,
+}
+
+/// Either a translation key or text computed by a factory registered under `source`.
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+pub enum TextDefinition {
+    Key(String),
+    Dynamic {
+        source: String,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+}
+
+#[derive(Deserialize, Debug)]
+pub enum ComponentDefinition {
+    Label(TextDefinition),
+    Button {
+        label: String,
+        action: ActionDefinition,
+    },
+    SelectableLabel {
+        label: String,
+        selected: ActionDefinition,
+        action: ActionDefinition,
+    },
+    Horizontal(Vec<ComponentDefinition>),
+    Vertical {
+        max_height: f32,
+        components: Vec<ComponentDefinition>,
+    },
+    Slider {
+        label: String,
+        value: ActionDefinition,
+        min: f32,
+        max: f32,
+        #[serde(default)]
+        step: Option<f64>,
+    },
+    Checkbox {
+        label: String,
+        value: ActionDefinition,
+    },
+    Dropdown {
+        label: String,
+        value: ActionDefinition,
+        options: Vec<OptionDefinition>,
+    },
+    TextInput {
+        label: String,
+        value: ActionDefinition,
+        #[serde(default)]
+        max_length: Option<usize>,
+    },
+    When {
+        condition: ActionDefinition,
+        component: Box<ComponentDefinition>,
+    },
+    Custom {
+        name: String,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+}
+
+#[derive(Deserialize, Debug)]
+pub struct OptionDefinition {
+    pub label: String,
+    pub value: String,
+    #[serde(default)]
+    pub count: Option<u32>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ActionDefinition {
+    pub name: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub confirm: Option<String>,
+}
+
+#[derive(Default)]
+pub struct MenuDefinitionLoader;
+
+#[derive(Debug)]
+pub enum MenuDefinitionLoaderError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+}
+
+impl fmt::Display for MenuDefinitionLoaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Could not read menu definition: {}", error),
+            Self::Ron(error) => write!(f, "Could not parse menu definition: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for MenuDefinitionLoaderError {}
+
+impl AssetLoader for MenuDefinitionLoader {
+    type Asset = MenuDefinition;
+    type Settings = ();
+    type Error = MenuDefinitionLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await.map_err(MenuDefinitionLoaderError::Io)?;
+
+        ron::de::from_bytes(&bytes).map_err(MenuDefinitionLoaderError::Ron)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["menu.ron"]
+    }
+}
+
+impl TextDefinition {
+    pub fn resolve(&self, registry: &MenuRegistry, translations: &Translations, world: &World) -> String {
+        match self {
+            TextDefinition::Key(key) => translations.text(key),
+            TextDefinition::Dynamic { source, args } => registry.create_text(world, source, args, translations),
+        }
+    }
+}
+
+impl OptionDefinition {
+    fn label(&self, translations: &Translations) -> String {
+        match self.count {
+            Some(count) => translations.plural(&self.label, count, &[]),
+            None => translations.text(&self.label),
+        }
+    }
+}
+
+impl ComponentDefinition {
+    /// Returns `None` for a `When` whose condition does not hold.
+    pub fn instantiate(
+        &self,
+        registry: &MenuRegistry,
+        translations: &Translations,
+        world: &World,
+    ) -> Option<Box<dyn MenuComponent>> {
+        let component: Box<dyn MenuComponent> = match self {
+            ComponentDefinition::Label(text) => Box::new(MenuLabel::new(text.resolve(registry, translations, world))),
+            ComponentDefinition::Button { label, action } => Box::new(
+                MenuButton::new(translations.text(label), registry.create_action(action, translations)),
+            ),
+            ComponentDefinition::SelectableLabel { label, selected, action } => Box::new(
+                MenuSelectableLabel::new(
+                    translations.text(label),
+                    registry.evaluate_condition(world, selected),
+                    registry.create_action(action, translations),
+                ),
+            ),
+            ComponentDefinition::Horizontal(components) => Box::new(
+                components.iter()
+                    .filter_map(|component| component.instantiate(registry, translations, world))
+                    .fold(MenuLayoutHorizontal::new(), MenuLayoutHorizontal::add_component),
+            ),
+            ComponentDefinition::Vertical { max_height, components } => Box::new(
+                components.iter()
+                    .filter_map(|component| component.instantiate(registry, translations, world))
+                    .fold(MenuLayoutVertical::new(*max_height), MenuLayoutVertical::add_component),
+            ),
+            ComponentDefinition::Slider { label, value, min, max, step } => {
+                let current = registry.read_value(world, value)
+                    .and_then(|value| value.as_number())
+                    .unwrap_or(*min);
+                let action = registry.create_value_action(value);
+
+                let slider = MenuSlider::new(
+                    translations.text(label),
+                    current,
+                    *min..=*max,
+                    ClosureMenuValueAction::new(move |value, commands: &mut Commands| {
+                        action.execute(MenuValue::Number(value), commands);
+                    }),
+                );
+                match step {
+                    Some(step) => Box::new(slider.with_step(*step)),
+                    None => Box::new(slider),
+                }
+            },
+            ComponentDefinition::Checkbox { label, value } => {
+                let checked = registry.read_value(world, value)
+                    .and_then(|value| value.as_bool())
+                    .unwrap_or_default();
+                let action = registry.create_value_action(value);
+
+                Box::new(MenuCheckbox::new(
+                    translations.text(label),
+                    checked,
+                    ClosureMenuValueAction::new(move |checked, commands: &mut Commands| {
+                        action.execute(MenuValue::Bool(checked), commands);
+                    }),
+                ))
+            },
+            ComponentDefinition::Dropdown { label, value, options } => {
+                let selected = registry.read_value(world, value)
+                    .and_then(|value| value.as_text().map(String::from))
+                    .unwrap_or_default();
+                let action = registry.create_value_action(value);
+
+                Box::new(options.iter().fold(
+                    MenuDropdown::new(
+                        translations.text(label),
+                        selected,
+                        ClosureMenuValueAction::new(move |selected, commands: &mut Commands| {
+                            action.execute(MenuValue::Text(selected), commands);
+                        }),
+                    ),
+                    |dropdown, option| dropdown.add_option(option.label(translations), option.value.clone()),
+                ))
+            },
+            ComponentDefinition::TextInput { label, value, max_length } => {
+                let text = registry.read_value(world, value)
+                    .and_then(|value| value.as_text().map(String::from))
+                    .unwrap_or_default();
+                let action = registry.create_value_action(value);
+
+                let input = MenuTextInput::new(
+                    translations.text(label),
+                    text,
+                    ClosureMenuValueAction::new(move |text, commands: &mut Commands| {
+                        action.execute(MenuValue::Text(text), commands);
+                    }),
+                );
+                match max_length {
+                    Some(max_length) => Box::new(input.with_max_length(*max_length)),
+                    None => Box::new(input),
+                }
+            },
+            ComponentDefinition::When { condition, component } => {
+                if !registry.evaluate_condition(world, condition) {
+                    return None;
+                }
+                return component.instantiate(registry, translations, world);
+            },
+            ComponentDefinition::Custom { name, args } => registry.create_component(world, name, args, translations),
+        };
+
+        Some(component)
+    }
+}
+
+pub fn show_menu_definition(
+    path: &'static str,
+) -> impl FnMut(&mut World, &mut SystemState<(EguiContexts<'static, 'static>, Commands<'static, 'static>)>) {
+    let mut handle: Option<Handle<MenuDefinition>> = None;
+
+    move |world, state| {
+        let handle = handle.get_or_insert_with(|| world.resource::<AssetServer>().load(path));
+        let Some(definition) = world.resource::<Assets<MenuDefinition>>().get(handle.id()) else {
+            return;
+        };
+
+        let builder = MenuBuilder::from_definition(
+            definition,
+            world.resource::<MenuRegistry>(),
+            world.resource::<Translations>(),
+            world,
+        );
+
+        let (contexts, mut commands) = state.get_mut(world);
+        builder.build(contexts, &mut commands);
+        state.apply(world);
+    }
+}
+
+pub struct MenuDefinitionPlugin;
+
+impl Plugin for MenuDefinitionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<MenuDefinition>()
+            .init_asset_loader::<MenuDefinitionLoader>()
+            .init_resource::<MenuRegistry>();
+    }
+}
@@ -0,0 +1,112 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use crate::game::states::MenuScreen;
+use crate::ui::menu::actions::MenuAction;
+
+struct MenuEntry {
+    screen: MenuScreen,
+    focus: Option<egui::Id>,
+}
+
+#[derive(Resource, Default)]
+pub struct MenuStack {
+    entries: Vec<MenuEntry>,
+    restore_focus: bool,
+}
+
+impl MenuStack {
+    pub fn current(&self) -> MenuScreen {
+        self.entries.last()
+            .map(|entry| entry.screen)
+            .unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn push(&mut self, screen: MenuScreen) {
+        self.entries.push(MenuEntry { screen, focus: None });
+    }
+
+    pub fn pop(&mut self) -> Option<MenuScreen> {
+        let entry = self.entries.pop()?;
+        self.restore_focus = true;
+        Some(entry.screen)
+    }
+
+    pub fn reset(&mut self, screen: MenuScreen) {
+        self.entries.clear();
+        self.push(screen);
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+pub struct PushMenuAction {
+    screen: MenuScreen,
+}
+
+impl PushMenuAction {
+    pub fn new(screen: MenuScreen) -> Self {
+        Self { screen }
+    }
+}
+
+impl MenuAction for PushMenuAction {
+    fn execute(&self, commands: &mut Commands) {
+        let screen = self.screen;
+        commands.queue(move |world: &mut World| {
+            world.resource_mut::<MenuStack>().push(screen);
+        });
+    }
+}
+
+pub struct PopMenuAction;
+
+impl MenuAction for PopMenuAction {
+    fn execute(&self, commands: &mut Commands) {
+        commands.queue(|world: &mut World| {
+            world.resource_mut::<MenuStack>().pop();
+        });
+    }
+}
+
+fn sync_menu_screen(
+    mut contexts: EguiContexts,
+    mut stack: ResMut<MenuStack>,
+    mut next_screen: ResMut<NextState<MenuScreen>>,
+) {
+    next_screen.set(stack.current());
+
+    if stack.restore_focus {
+        stack.restore_focus = false;
+        if let Some(focus) = stack.entries.last().and_then(|entry| entry.focus) {
+            contexts.ctx_mut().memory_mut(|memory| memory.request_focus(focus));
+        }
+    }
+}
+
+fn remember_menu_focus(mut contexts: EguiContexts, mut stack: ResMut<MenuStack>) {
+    let focus = contexts.ctx_mut().memory(|memory| memory.focused());
+
+    if let Some(entry) = stack.bypass_change_detection().entries.last_mut() {
+        if focus.is_some() {
+            entry.focus = focus;
+        }
+    }
+}
+
+pub struct MenuNavigationPlugin;
+
+impl Plugin for MenuNavigationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MenuStack>()
+            .add_systems(Update, (
+                sync_menu_screen.run_if(resource_changed::<MenuStack>),
+                remember_menu_focus,
+            ));
+    }
+}
@@ -0,0 +1,237 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use std::sync::Arc;
+use crate::ui::menu::actions::{
+    ClosureMenuAction, ClosureMenuValueAction, ConfirmMenuAction, MenuAction, MenuValue, MenuValueAction,
+};
+use crate::ui::menu::components::{MenuComponent, MenuLabel};
+use crate::ui::menu::definition::ActionDefinition;
+use crate::locale::Translations;
+
+type ActionFactory = Box<dyn Fn(&[String]) -> Option<Box<dyn MenuAction>> + Send + Sync>;
+type ConditionFactory = Box<dyn Fn(&World, &[String]) -> bool + Send + Sync>;
+type ComponentFactory = Box<dyn Fn(&World, &[String], &Translations) -> Option<Box<dyn MenuComponent>> + Send + Sync>;
+type TextFactory = Box<dyn Fn(&World, &[String], &Translations) -> Option<String> + Send + Sync>;
+type ValueGetter = Box<dyn Fn(&World, &[String]) -> Option<MenuValue> + Send + Sync>;
+type ValueSetter = Arc<dyn Fn(&mut World, &[String], MenuValue) + Send + Sync>;
+
+#[derive(Resource, Default)]
+pub struct MenuRegistry {
+    actions: HashMap<String, ActionFactory>,
+    conditions: HashMap<String, ConditionFactory>,
+    components: HashMap<String, ComponentFactory>,
+    texts: HashMap<String, TextFactory>,
+    values: HashMap<String, (ValueGetter, ValueSetter)>,
+}
+
+impl MenuRegistry {
+    pub fn register_action(
+        &mut self,
+        name: impl Into<String>,
+        factory: impl Fn(&[String]) -> Option<Box<dyn MenuAction>> + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.actions.insert(name.into(), Box::new(factory));
+        self
+    }
+
+    pub fn register_condition(
+        &mut self,
+        name: impl Into<String>,
+        condition: impl Fn(&World, &[String]) -> bool + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.conditions.insert(name.into(), Box::new(condition));
+        self
+    }
+
+    pub fn register_component(
+        &mut self,
+        name: impl Into<String>,
+        factory: impl Fn(&World, &[String], &Translations) -> Option<Box<dyn MenuComponent>> + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.components.insert(name.into(), Box::new(factory));
+        self
+    }
+
+    pub fn register_text(
+        &mut self,
+        name: impl Into<String>,
+        factory: impl Fn(&World, &[String], &Translations) -> Option<String> + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.texts.insert(name.into(), Box::new(factory));
+        self
+    }
+
+    pub fn register_value(
+        &mut self,
+        name: impl Into<String>,
+        get: impl Fn(&World, &[String]) -> Option<MenuValue> + Send + Sync + 'static,
+        set: impl Fn(&mut World, &[String], MenuValue) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.values.insert(name.into(), (Box::new(get), Arc::new(set)));
+        self
+    }
+
+    pub fn create_action(&self, definition: &ActionDefinition, translations: &Translations) -> Box<dyn MenuAction> {
+        let action = self.actions.get(&definition.name)
+            .and_then(|factory| factory(&definition.args));
+
+        match (action, &definition.confirm) {
+            (Some(action), Some(message)) => Box::new(ConfirmMenuAction::new(translations.text(message), action)),
+            (Some(action), None) => action,
+            (None, _) => {
+                warn!("Unknown menu action {:?} with arguments {:?}", definition.name, definition.args);
+                Box::new(ClosureMenuAction::new(|_: &mut Commands| {}))
+            }
+        }
+    }
+
+    pub fn create_component(
+        &self,
+        world: &World,
+        name: &str,
+        args: &[String],
+        translations: &Translations,
+    ) -> Box<dyn MenuComponent> {
+        let component = self.components.get(name)
+            .and_then(|factory| factory(world, args, translations));
+
+        match component {
+            Some(component) => component,
+            None => {
+                warn!("Unknown menu component {:?} with arguments {:?}", name, args);
+                Box::new(MenuLabel::new(""))
+            }
+        }
+    }
+
+    pub fn create_text(
+        &self,
+        world: &World,
+        name: &str,
+        args: &[String],
+        translations: &Translations,
+    ) -> String {
+        let text = self.texts.get(name)
+            .and_then(|factory| factory(world, args, translations));
+
+        text.unwrap_or_else(|| {
+            warn!("Unknown menu text {:?} with arguments {:?}", name, args);
+            String::new()
+        })
+    }
+
+    pub fn read_value(&self, world: &World, definition: &ActionDefinition) -> Option<MenuValue> {
+        match self.values.get(&definition.name) {
+            Some((get, _)) => get(world, &definition.args),
+            None => {
+                warn!("Unknown menu value {:?}", definition.name);
+                None
+            }
+        }
+    }
+
+    pub fn create_value_action(&self, definition: &ActionDefinition) -> Box<dyn MenuValueAction<MenuValue>> {
+        let Some((_, set)) = self.values.get(&definition.name) else {
+            warn!("Unknown menu value {:?}", definition.name);
+            return Box::new(ClosureMenuValueAction::new(|_: MenuValue, _: &mut Commands| {}));
+        };
+
+        let set = set.clone();
+        let args = definition.args.clone();
+        Box::new(ClosureMenuValueAction::new(move |value: MenuValue, commands: &mut Commands| {
+            let (set, args) = (set.clone(), args.clone());
+            commands.queue(move |world: &mut World| set(world, &args, value));
+        }))
+    }
+
+    pub fn evaluate_condition(&self, world: &World, definition: &ActionDefinition) -> bool {
+        match self.conditions.get(&definition.name) {
+            Some(condition) => condition(world, &definition.args),
+            None => {
+                warn!("Unknown menu condition {:?}", definition.name);
+                false
+            }
+        }
+    }
+}
+
+pub trait MenuRegistryAppExt {
+    fn register_menu_action(
+        &mut self,
+        name: impl Into<String>,
+        factory: impl Fn(&[String]) -> Option<Box<dyn MenuAction>> + Send + Sync + 'static,
+    ) -> &mut Self;
+
+    fn register_menu_condition(
+        &mut self,
+        name: impl Into<String>,
+        condition: impl Fn(&World, &[String]) -> bool + Send + Sync + 'static,
+    ) -> &mut Self;
+
+    fn register_menu_component(
+        &mut self,
+        name: impl Into<String>,
+        factory: impl Fn(&World, &[String], &Translations) -> Option<Box<dyn MenuComponent>> + Send + Sync + 'static,
+    ) -> &mut Self;
+
+    fn register_menu_text(
+        &mut self,
+        name: impl Into<String>,
+        factory: impl Fn(&World, &[String], &Translations) -> Option<String> + Send + Sync + 'static,
+    ) -> &mut Self;
+
+    fn register_menu_value(
+        &mut self,
+        name: impl Into<String>,
+        get: impl Fn(&World, &[String]) -> Option<MenuValue> + Send + Sync + 'static,
+        set: impl Fn(&mut World, &[String], MenuValue) + Send + Sync + 'static,
+    ) -> &mut Self;
+}
+
+impl MenuRegistryAppExt for App {
+    fn register_menu_action(
+        &mut self,
+        name: impl Into<String>,
+        factory: impl Fn(&[String]) -> Option<Box<dyn MenuAction>> + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.world_mut().get_resource_or_init::<MenuRegistry>().register_action(name, factory);
+        self
+    }
+
+    fn register_menu_condition(
+        &mut self,
+        name: impl Into<String>,
+        condition: impl Fn(&World, &[String]) -> bool + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.world_mut().get_resource_or_init::<MenuRegistry>().register_condition(name, condition);
+        self
+    }
+
+    fn register_menu_component(
+        &mut self,
+        name: impl Into<String>,
+        factory: impl Fn(&World, &[String], &Translations) -> Option<Box<dyn MenuComponent>> + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.world_mut().get_resource_or_init::<MenuRegistry>().register_component(name, factory);
+        self
+    }
+
+    fn register_menu_text(
+        &mut self,
+        name: impl Into<String>,
+        factory: impl Fn(&World, &[String], &Translations) -> Option<String> + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.world_mut().get_resource_or_init::<MenuRegistry>().register_text(name, factory);
+        self
+    }
+
+    fn register_menu_value(
+        &mut self,
+        name: impl Into<String>,
+        get: impl Fn(&World, &[String]) -> Option<MenuValue> + Send + Sync + 'static,
+        set: impl Fn(&mut World, &[String], MenuValue) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.world_mut().get_resource_or_init::<MenuRegistry>().register_value(name, get, set);
+        self
+    }
+}
\ No newline at end of file
@@ -1,35 +1,42 @@
 use bevy::prelude::*;
-use bevy_egui::{EguiContexts, EguiPlugin};
+use bevy::input::gamepad::GamepadConnectionEvent;
+use bevy::window::WindowFocused;
+use bevy_egui::EguiContexts;
 use leafwing_input_manager::prelude::*;
 
 use crate::game::{
     states::{
-        GameState, PausedState, StartGameSet,
-        MainSet, ControlsSet, PlayingSet, PausedSet,
-        EndgameSet,
+        GameState, PausedState, MenuScreen, StartGameSet,
+        MainSet, ControlsSet, PausedSet,
+        SettingsSet, RulesSet, ProfilesSet, TrainingSet, EndgameSet,
     },
     controls::{GameAction, ControlRemapping, listen_for_keys},
-    settings::{GameSettings, Difficulty, PlayerType},
-    commands::UpdatePlayerCommand,
+    settings::{GameSettings, PlayerType},
+    commands::{
+        UpdatePlayerCommand, ResetControlsCommand,
+        UpdateProfileCommand, CreateProfileCommand, DeleteProfileCommand,
+        SwapSidesCommand, ChangeDifficultyCommand, StartGameCommand,
+        SelectDrillCommand, SaveDrillCommand,
+    },
+    profiles::{PlayerProfile, PlayerProfiles, PROFILE_COLORS},
+    drills::{Drill, DrillPresets, SelectedDrill},
 };
-use crate::pong::Score;
+use crate::pong::{Rules, Score, Series};
+use crate::locale::{LocalizationPlugin, Localization, Translations};
 use crate::ui::menu::{
-    builder::MenuBuilder,
-    components::{MenuButton, MenuLabel, MenuLayoutHorizontal, MenuSelectableLabel},
-    actions::{ChangeStateMenuAction, QuitMenuAction, UpdateResourceMenuAction, CommandMenuAction}
+    components::{
+        MenuButton, MenuConfirmDialog, MenuDropdown, MenuLabel, MenuLayoutHorizontal,
+        MenuLayoutVertical, MenuComponent,
+    },
+    actions::{
+        ChangeStateMenuAction, QuitMenuAction, ClosureMenuAction, UpdateResourceMenuAction, CommandMenuAction,
+        ClosureMenuValueAction, ConfirmMenuAction, MenuAction, MenuConfirmation, MenuValue,
+    },
+    definition::{MenuDefinitionPlugin, show_menu_definition},
+    navigation::{MenuNavigationPlugin, MenuStack, PopMenuAction, PushMenuAction},
+    registry::MenuRegistryAppExt,
 };
 
-fn main_menu(mut commands: Commands, contexts: EguiContexts) {
-    let builder = MenuBuilder::new("Pong!");
-    builder.add_component(
-        MenuButton::new("Start Game", ChangeStateMenuAction::new(GameState::Startgame)),
-    ).add_component(
-        MenuButton::new("Controls", ChangeStateMenuAction::new(GameState::Controls)),
-    ).add_component(
-        MenuButton::new("Quit Game", QuitMenuAction),
-    ).build(contexts, &mut commands);
-}
-
 fn init_controls_menu(mut commands: Commands) {
     commands.insert_resource(ControlRemapping::default());
 }
@@ -38,31 +45,8 @@ fn destroy_controls_menu(mut commands: Commands) {
     commands.remove_resource::<ControlRemapping>();
 }
 
-fn controls_menu(
-    mut commands: Commands,
-    contexts: EguiContexts,
-    keys: Res<InputMap<GameAction>>,
-) {
-    let mut builder = MenuBuilder::new("Controls")
-        .with_top_spacing(25.)
-        .add_component(MenuLabel::new("Player 1:"));
-
-    builder = control_selection_button(&keys, builder, GameAction::Player1Up);
-    builder = control_selection_button(&keys, builder, GameAction::Player1Down);
-    builder = builder.add_component(MenuLabel::new("Player 2:"));
-    builder = control_selection_button(&keys, builder, GameAction::Player2Up);
-    builder = control_selection_button(&keys, builder, GameAction::Player2Down);
-    builder = builder.add_component(MenuLabel::new(""));
-
-    builder = control_selection_button(&keys, builder, GameAction::Menu);
-
-    builder.add_component(
-        MenuButton::new("Back", ChangeStateMenuAction::new(GameState::Main)),
-    ).build(contexts, &mut commands);
-}
-
-fn control_selection_button(keys: &Res<InputMap<GameAction>>, builder: MenuBuilder, control: GameAction) -> MenuBuilder {
-    let current_keys = keys.get(&control)
+fn control_binding(world: &World, control: GameAction, tr: &Translations) -> MenuLayoutHorizontal {
+    let current_keys = world.resource::<InputMap<GameAction>>().get(&control)
         .map(|key_set| {
             key_set.iter().filter_map(|key| {
                 match key {
@@ -70,121 +54,476 @@ fn control_selection_button(keys: &Res<InputMap<GameAction>>, builder: MenuBuild
                     _ => None,
                 }
             }).collect::<Vec<String>>().join(", ")
-        }).unwrap_or_else(|| "[Not Set]".to_string());
+        }).unwrap_or_else(|| tr.text("controls.not_set"));
 
-    builder.add_component(
-        MenuLayoutHorizontal::new()
-            .add_component(MenuLabel::new(format!("{:?}", control)))
-            .add_component(MenuButton::new(current_keys, UpdateResourceMenuAction::new(ControlRemapping::start_remapping(control)))),
-    )
+    MenuLayoutHorizontal::new()
+        .add_component(MenuLabel::new(tr.text(&format!("action.{:?}", control))))
+        .add_component(MenuButton::new(current_keys, UpdateResourceMenuAction::new(ControlRemapping::start_remapping(control))))
 }
 
-fn init_start_game_menu(mut commands: Commands) {
-    commands.init_resource::<GameSettings>();
-}
-
-fn start_game_menu(mut commands: Commands, contexts: EguiContexts, settings: ResMut<GameSettings>) {
-    MenuBuilder::new("New Game")
-        .with_top_spacing(100.)
-        .add_component(MenuLabel::new("Player 1"))
-        .add_component(MenuLayoutHorizontal::new()
-            .add_component(MenuSelectableLabel::new(
-                "Human",
-                matches!(settings.get_player1(), PlayerType::Human),
-                CommandMenuAction::new(UpdatePlayerCommand::new(1, PlayerType::Human))
-            ))
-            .add_component(MenuSelectableLabel::new(
-                "Easy",
-                matches!(settings.get_player1(), PlayerType::Computer(difficulty) if difficulty == &Difficulty::Easy),
-                CommandMenuAction::new(UpdatePlayerCommand::new(1, PlayerType::Computer(Difficulty::Easy)))
-            ))
-            .add_component(MenuSelectableLabel::new(
-                "Difficult",
-                matches!(settings.get_player1(), PlayerType::Computer(difficulty) if difficulty == &Difficulty::Difficult),
-                CommandMenuAction::new(UpdatePlayerCommand::new(1, PlayerType::Computer(Difficulty::Difficult)))
-            ))
-            .add_component(MenuSelectableLabel::new(
-                "Impossible",
-                matches!(settings.get_player1(), PlayerType::Computer(difficulty) if difficulty == &Difficulty::Impossible),
-                CommandMenuAction::new(UpdatePlayerCommand::new(1, PlayerType::Computer(Difficulty::Impossible)))
-            ))
-        )
-        .add_component(MenuLabel::new("Player 2"))
-        .add_component(MenuLayoutHorizontal::new()
-            .add_component(MenuSelectableLabel::new(
-                "Human",
-                matches!(settings.get_player2(), PlayerType::Human),
-                CommandMenuAction::new(UpdatePlayerCommand::new(2, PlayerType::Human))
-            ))
-            .add_component(MenuSelectableLabel::new(
-                "Easy",
-                matches!(settings.get_player2(), PlayerType::Computer(difficulty) if difficulty == &Difficulty::Easy),
-                CommandMenuAction::new(UpdatePlayerCommand::new(2, PlayerType::Computer(Difficulty::Easy)))
-            ))
-            .add_component(MenuSelectableLabel::new(
-                "Difficult",
-                matches!(settings.get_player2(), PlayerType::Computer(difficulty) if difficulty == &Difficulty::Difficult),
-                CommandMenuAction::new(UpdatePlayerCommand::new(2, PlayerType::Computer(Difficulty::Difficult)))
-            ))
-            .add_component(MenuSelectableLabel::new(
-                "Impossible",
-                matches!(settings.get_player2(), PlayerType::Computer(difficulty) if difficulty == &Difficulty::Impossible),
-                CommandMenuAction::new(UpdatePlayerCommand::new(2, PlayerType::Computer(Difficulty::Impossible)))
-            ))
+#[derive(Resource, Default)]
+struct NewProfile {
+    name: String,
+    color: usize,
+}
+
+fn init_profiles_menu(mut commands: Commands) {
+    commands.init_resource::<NewProfile>();
+}
+
+fn destroy_profiles_menu(mut commands: Commands) {
+    commands.remove_resource::<NewProfile>();
+}
+
+fn profile_list(world: &World, tr: &Translations) -> MenuLayoutVertical {
+    world.resource::<PlayerProfiles>().iter().fold(MenuLayoutVertical::new(300.), |list, profile| {
+        let stats = tr.format("profiles.stats", &[
+            ("won", &profile.stats.matches_won.to_string()),
+            ("played", &profile.stats.matches_played.to_string()),
+        ]);
+
+        list.add_component(MenuLayoutHorizontal::new()
+            .add_component(MenuLabel::new(&profile.name))
+            .add_component(MenuLabel::new(stats))
+            .add_component(MenuButton::new(tr.text("profiles.delete"), ConfirmMenuAction::new(
+                tr.format("profiles.delete_confirm", &[("name", &profile.name)]),
+                CommandMenuAction::new(DeleteProfileCommand::new(&profile.name)),
+            )))
         )
-        .add_component(MenuButton::new("Start Game", ChangeStateMenuAction::new(GameState::Playing)))
-        .add_component(MenuButton::new("Back", ChangeStateMenuAction::new(GameState::Main)))
-        .build(contexts, &mut commands);
+    })
+}
+
+fn new_profile_value(new_profile: &NewProfile, field: &str) -> Option<MenuValue> {
+    match field {
+        "name" => Some(MenuValue::Text(new_profile.name.clone())),
+        "color" => Some(MenuValue::Text(PROFILE_COLORS[new_profile.color].0.to_string())),
+        _ => None,
+    }
 }
 
-fn toggle_pause_game(
+fn update_new_profile(new_profile: &mut NewProfile, field: &str, value: MenuValue) {
+    match (field, value) {
+        ("name", MenuValue::Text(name)) => new_profile.name = name,
+        ("color", MenuValue::Text(color)) => {
+            if let Some(index) = PROFILE_COLORS.iter().position(|(name, _)| *name == color) {
+                new_profile.color = index;
+            }
+        },
+        (field, value) => warn!("Cannot set new profile {:?} to {:?}", field, value),
+    }
+}
+
+fn drill_select(world: &World, tr: &Translations) -> MenuDropdown<String> {
+    world.resource::<DrillPresets>().iter().fold(
+        MenuDropdown::new(
+            tr.text("training.preset"),
+            world.resource::<SelectedDrill>().0.name.clone(),
+            ClosureMenuValueAction::new(|name: String, commands: &mut Commands| {
+                commands.queue(SelectDrillCommand::new(name));
+            }),
+        ),
+        |dropdown, preset| dropdown.add_option(&preset.name, preset.name.clone()),
+    )
+}
+
+fn drill_value(drill: &Drill, field: &str) -> Option<MenuValue> {
+    match field {
+        "name" => Some(MenuValue::Text(drill.name.clone())),
+        "min_speed" => Some(MenuValue::Number(drill.min_speed)),
+        "max_speed" => Some(MenuValue::Number(drill.max_speed)),
+        "min_angle" => Some(MenuValue::Number(drill.min_angle)),
+        "max_angle" => Some(MenuValue::Number(drill.max_angle)),
+        "spin" => Some(MenuValue::Number(drill.spin)),
+        "interval" => Some(MenuValue::Number(drill.interval)),
+        "target" => Some(MenuValue::Text(drill.target.name().to_string())),
+        _ => None,
+    }
+}
+
+fn update_drill(drill: &mut Drill, field: &str, value: MenuValue) {
+    match (field, value) {
+        ("name", MenuValue::Text(name)) => drill.name = name,
+        ("min_speed", MenuValue::Number(speed)) => {
+            drill.min_speed = speed;
+            drill.max_speed = drill.max_speed.max(speed);
+        },
+        ("max_speed", MenuValue::Number(speed)) => {
+            drill.max_speed = speed;
+            drill.min_speed = drill.min_speed.min(speed);
+        },
+        ("min_angle", MenuValue::Number(angle)) => {
+            drill.min_angle = angle;
+            drill.max_angle = drill.max_angle.max(angle);
+        },
+        ("max_angle", MenuValue::Number(angle)) => {
+            drill.max_angle = angle;
+            drill.min_angle = drill.min_angle.min(angle);
+        },
+        ("spin", MenuValue::Number(spin)) => drill.spin = spin,
+        ("interval", MenuValue::Number(interval)) => drill.interval = interval,
+        ("target", MenuValue::Text(target)) => {
+            if let Ok(target) = target.parse() {
+                drill.target = target;
+            }
+        },
+        (field, value) => warn!("Cannot set drill {:?} to {:?}", field, value),
+    }
+}
+
+fn profile_select(world: &World, player_num: usize, tr: &Translations) -> MenuDropdown<Option<String>> {
+    let selected = world.resource::<GameSettings>()
+        .get_profile(player_num)
+        .map(String::from);
+
+    world.resource::<PlayerProfiles>().iter().fold(
+        MenuDropdown::new(
+            tr.text("start_game.profile"),
+            selected,
+            ClosureMenuValueAction::new(move |profile, commands: &mut Commands| {
+                commands.queue(UpdateProfileCommand::new(player_num, profile));
+            }),
+        ).add_option(tr.text("start_game.no_profile"), None),
+        |dropdown, profile| dropdown.add_option(&profile.name, Some(profile.name.clone())),
+    )
+}
+
+fn player_name(settings: &GameSettings, player_num: usize, tr: &Translations) -> String {
+    settings.get_profile(player_num)
+        .map(String::from)
+        .unwrap_or_else(|| tr.format("player.name", &[("num", &player_num.to_string())]))
+}
+
+fn confirmation_dialog(
+    mut commands: Commands,
+    mut contexts: EguiContexts,
+    confirmation: Res<MenuConfirmation>,
+    tr: Res<Translations>,
+) {
+    MenuConfirmDialog::new(confirmation.clone())
+        .with_labels(tr.text("dialog.yes"), tr.text("dialog.no"))
+        .show(contexts.ctx_mut(), &mut commands);
+}
+
+fn navigate_back(
+    mut commands: Commands,
     keys: Res<ActionState<GameAction>>,
-    state: Res<State<PausedState>>,
-    mut next_state: ResMut<NextState<PausedState>>,
+    state: Res<State<GameState>>,
+    remapping: Option<Res<ControlRemapping>>,
+    confirmation: Option<Res<MenuConfirmation>>,
+    mut stack: ResMut<MenuStack>,
 ) {
-    if keys.just_pressed(&GameAction::Menu) {
-        match state.get() {
-            PausedState::Playing => next_state.set(PausedState::Paused),
-            PausedState::Paused => next_state.set(PausedState::Playing),
+    if !keys.just_pressed(&GameAction::Menu) || remapping.is_some_and(|remapping| remapping.is_listening()) {
+        return;
+    }
+
+    if confirmation.is_some() {
+        commands.remove_resource::<MenuConfirmation>();
+    } else if stack.is_empty() {
+        if state.get() == &GameState::Playing {
+            stack.push(MenuScreen::Paused);
         }
+    } else if !stack.current().is_root() {
+        stack.pop();
+    }
+}
+
+fn auto_pause(
+    mut focus_events: EventReader<WindowFocused>,
+    mut gamepad_events: EventReader<GamepadConnectionEvent>,
+    settings: Res<GameSettings>,
+    mut stack: ResMut<MenuStack>,
+) {
+    let focus_lost = focus_events.read().any(|event| !event.focused);
+    let disconnected = gamepad_events.read().any(|event| event.disconnected());
+
+    if (focus_lost || disconnected) && settings.is_auto_pause() && stack.is_empty() {
+        stack.push(MenuScreen::Paused);
+    }
+}
+
+fn sync_paused_state(stack: Res<MenuStack>, mut next_state: ResMut<NextState<PausedState>>) {
+    if stack.is_empty() {
+        next_state.set(PausedState::Playing);
+    } else {
+        next_state.set(PausedState::Paused);
+    }
+}
+
+fn reset_menu_stack(screen: MenuScreen) -> impl Fn(ResMut<MenuStack>) {
+    move |mut stack| stack.reset(screen)
+}
+
+fn clear_menu_stack(mut stack: ResMut<MenuStack>) {
+    stack.clear();
+}
+
+fn parse_game_state(name: &str) -> Option<GameState> {
+    match name {
+        "Main" => Some(GameState::Main),
+        "Playing" => Some(GameState::Playing),
+        "Endgame" => Some(GameState::Endgame),
+        _ => None,
     }
 }
 
-fn paused_menu(mut commands: Commands, contexts: EguiContexts)  {
-    let builder = MenuBuilder::new("Paused");
-    builder.add_component(
-        MenuButton::new("Resume", ChangeStateMenuAction::new(PausedState::Playing))
-    ).add_component(
-        MenuButton::new("Main Menu", ChangeStateMenuAction::new(GameState::Main)),
-    ).build(contexts, &mut commands);
+fn parse_menu_screen(name: &str) -> Option<MenuScreen> {
+    match name {
+        "Main" => Some(MenuScreen::Main),
+        "Controls" => Some(MenuScreen::Controls),
+        "StartGame" => Some(MenuScreen::StartGame),
+        "Paused" => Some(MenuScreen::Paused),
+        "Settings" => Some(MenuScreen::Settings),
+        "Rules" => Some(MenuScreen::Rules),
+        "Profiles" => Some(MenuScreen::Profiles),
+        "Training" => Some(MenuScreen::Training),
+        "Endgame" => Some(MenuScreen::Endgame),
+        _ => None,
+    }
 }
 
-fn end_game_menu(mut commands: Commands, contexts: EguiContexts, score: Res<Score>)  {
-    let title = format!("{} wins!", score.get_winner());
+fn parse_player_args(args: &[String]) -> Option<(usize, PlayerType)> {
+    match args {
+        [player_num, player_type] => Some((player_num.parse().ok()?, player_type.parse().ok()?)),
+        _ => None,
+    }
+}
 
-    let builder = MenuBuilder::new(title);
-    builder.add_component(
-        MenuButton::new("Restart", ChangeStateMenuAction::new(GameState::Playing))
-    ).add_component(
-        MenuButton::new("Quit", ChangeStateMenuAction::new(GameState::Main))
-    ).build(contexts, &mut commands);
+fn parse_game_action(name: &str) -> Option<GameAction> {
+    match name {
+        "Player1Up" => Some(GameAction::Player1Up),
+        "Player1Down" => Some(GameAction::Player1Down),
+        "Player2Up" => Some(GameAction::Player2Up),
+        "Player2Down" => Some(GameAction::Player2Down),
+        "Menu" => Some(GameAction::Menu),
+        _ => None,
+    }
 }
 
-pub struct MenuSystemsPlugin;
+fn register_menu_actions(app: &mut App) {
+    app.register_menu_action("game_state", |args| {
+        let state = parse_game_state(args.first()?)?;
+        Some(Box::new(ChangeStateMenuAction::new(state)) as Box<dyn MenuAction>)
+    }).register_menu_action("start_game", |args| {
+        let mode = args.first()?.parse().ok()?;
+        Some(Box::new(CommandMenuAction::new(StartGameCommand::new(mode))) as Box<dyn MenuAction>)
+    }).register_menu_action("push_menu", |args| {
+        let screen = parse_menu_screen(args.first()?)?;
+        Some(Box::new(PushMenuAction::new(screen)) as Box<dyn MenuAction>)
+    }).register_menu_action("pop_menu", |_| {
+        Some(Box::new(PopMenuAction) as Box<dyn MenuAction>)
+    }).register_menu_action("quit", |_| {
+        Some(Box::new(QuitMenuAction) as Box<dyn MenuAction>)
+    }).register_menu_action("player_type", |args| {
+        let (player_num, player_type) = parse_player_args(args)?;
+        Some(Box::new(CommandMenuAction::new(UpdatePlayerCommand::new(player_num, player_type))) as Box<dyn MenuAction>)
+    }).register_menu_action("reset_controls", |_| {
+        Some(Box::new(CommandMenuAction::new(ResetControlsCommand)) as Box<dyn MenuAction>)
+    }).register_menu_action("swap_sides", |_| {
+        Some(Box::new(ClosureMenuAction::new(|commands| {
+            commands.queue(SwapSidesCommand);
+            commands.queue(|world: &mut World| world.resource_mut::<Series>().swap());
+            commands.set_state(GameState::Playing);
+        })) as Box<dyn MenuAction>)
+    }).register_menu_action("change_difficulty", |args| {
+        let command = match args.first()?.as_str() {
+            "easier" => ChangeDifficultyCommand::easier(),
+            "harder" => ChangeDifficultyCommand::harder(),
+            _ => return None,
+        };
+        Some(Box::new(ClosureMenuAction::new(move |commands| {
+            commands.queue(command.clone());
+            commands.set_state(GameState::Playing);
+        })) as Box<dyn MenuAction>)
+    }).register_menu_action("create_profile", |_| {
+        Some(Box::new(ClosureMenuAction::new(|commands| {
+            commands.queue(|world: &mut World| {
+                let Some(new_profile) = world.remove_resource::<NewProfile>() else {
+                    return;
+                };
+                let profile = PlayerProfile::new(new_profile.name.trim(), PROFILE_COLORS[new_profile.color].1);
+                CreateProfileCommand::new(profile).apply(world);
+                world.init_resource::<NewProfile>();
+            });
+        })) as Box<dyn MenuAction>)
+    }).register_menu_action("save_drill", |_| {
+        Some(Box::new(ClosureMenuAction::new(|commands| {
+            commands.queue(|world: &mut World| {
+                let drill = world.resource::<SelectedDrill>().0.clone();
+                SaveDrillCommand::new(drill).apply(world);
+            });
+        })) as Box<dyn MenuAction>)
+    }).register_menu_condition("player_type", |world, args| {
+        let (Some(settings), Some((player_num, player_type))) = (world.get_resource::<GameSettings>(), parse_player_args(args)) else {
+            return false;
+        };
+
+        match player_num {
+            1 => settings.get_player1() == &player_type,
+            2 => settings.get_player2() == &player_type,
+            _ => false,
+        }
+    }).register_menu_condition("has_computer", |world, _| {
+        let settings = world.resource::<GameSettings>();
+        [settings.get_player1(), settings.get_player2()].into_iter()
+            .any(|player_type| matches!(player_type, PlayerType::Computer(_)))
+    }).register_menu_condition("series", |world, _| {
+        world.resource::<Series>().best_of() > 1
+    }).register_menu_condition("series_decided", |world, _| {
+        let series = world.resource::<Series>();
+        series.best_of() > 1 && series.is_decided()
+    }).register_menu_component("profile_select", |world, args, tr| {
+        let player_num = args.first()?.parse().ok()?;
+        Some(Box::new(profile_select(world, player_num, tr)) as Box<dyn MenuComponent>)
+    }).register_menu_component("control_binding", |world, args, tr| {
+        let control = parse_game_action(args.first()?)?;
+        Some(Box::new(control_binding(world, control, tr)) as Box<dyn MenuComponent>)
+    }).register_menu_component("profile_list", |world, _, tr| {
+        Some(Box::new(profile_list(world, tr)) as Box<dyn MenuComponent>)
+    }).register_menu_component("drill_select", |world, _, tr| {
+        Some(Box::new(drill_select(world, tr)) as Box<dyn MenuComponent>)
+    });
+
+    register_menu_texts(app);
+    register_menu_values(app);
+}
+
+fn register_menu_texts(app: &mut App) {
+    app.register_menu_text("match_winner", |world, _, tr| {
+        let Some(winner) = world.resource::<Score>().get_winner_num() else {
+            return Some(tr.text("endgame.draw"));
+        };
+        let winner = player_name(world.resource::<GameSettings>(), winner, tr);
+        Some(tr.format("endgame.winner", &[("winner", &winner)]))
+    }).register_menu_text("series_score", |world, _, tr| {
+        let series = world.resource::<Series>();
+        let (player1, player2) = series.wins();
+        Some(tr.format("endgame.series", &[
+            ("player1", &player1.to_string()),
+            ("player2", &player2.to_string()),
+            ("best_of", &series.best_of().to_string()),
+        ]))
+    }).register_menu_text("series_winner", |world, _, tr| {
+        let winner = player_name(world.resource::<GameSettings>(), world.resource::<Series>().get_winner_num()?, tr);
+        Some(tr.format("endgame.series_winner", &[("winner", &winner)]))
+    }).register_menu_text("target_score", |world, _, tr| {
+        Some(tr.plural("rules.target", world.resource::<Rules>().target_score, &[]))
+    }).register_menu_text("drill_stats", |world, _, tr| {
+        let drill = &world.resource::<SelectedDrill>().0;
+        let stats = world.resource::<DrillPresets>().get(&drill.name)
+            .map(|preset| tr.format("training.stats", &[
+                ("returns", &preset.stats.returns.to_string()),
+                ("launched", &preset.stats.launched.to_string()),
+                ("accuracy", &format!("{:.0}", preset.stats.accuracy())),
+            ]))
+            .unwrap_or_default();
+        Some(stats)
+    });
+}
+
+fn register_menu_values(app: &mut App) {
+    app.register_menu_value("language", |world, _| {
+        Some(MenuValue::Text(world.resource::<Localization>().language().to_string()))
+    }, |world, _, value| {
+        if let MenuValue::Text(language) = value {
+            world.resource_mut::<Localization>().set_language(language);
+        }
+    }).register_menu_value("player_type", |world, args| {
+        let player_num = args.first()?.parse().ok()?;
+        let settings = world.resource::<GameSettings>();
+        let player_type = match player_num {
+            1 => settings.get_player1(),
+            2 => settings.get_player2(),
+            _ => return None,
+        };
+        Some(MenuValue::Text(player_type.to_string()))
+    }, |world, args, value| {
+        let player_num = args.first().and_then(|num| num.parse().ok());
+        let player_type = value.as_text().and_then(|name| name.parse().ok());
+        if let (Some(player_num @ (1 | 2)), Some(player_type)) = (player_num, player_type) {
+            UpdatePlayerCommand::new(player_num, player_type).apply(world);
+        }
+    }).register_menu_value("series_length", |world, _| {
+        Some(MenuValue::Text(world.resource::<GameSettings>().get_series_length().to_string()))
+    }, |world, _, value| {
+        if let Some(length) = value.as_text().and_then(|length| length.parse().ok()) {
+            world.resource_mut::<GameSettings>().set_series_length(length);
+        }
+    }).register_menu_value("auto_pause", |world, _| {
+        Some(MenuValue::Bool(world.resource::<GameSettings>().is_auto_pause()))
+    }, |world, _, value| {
+        if let Some(enabled) = value.as_bool() {
+            world.resource_mut::<GameSettings>().set_auto_pause(enabled);
+        }
+    }).register_menu_value("new_profile", |world, args| {
+        new_profile_value(world.get_resource::<NewProfile>()?, args.first()?)
+    }, |world, args, value| {
+        if let (Some(mut new_profile), Some(field)) = (world.get_resource_mut::<NewProfile>(), args.first()) {
+            update_new_profile(&mut new_profile, field, value);
+        }
+    }).register_menu_value("drill", |world, args| {
+        drill_value(&world.resource::<SelectedDrill>().0, args.first()?)
+    }, |world, args, value| {
+        if let Some(field) = args.first() {
+            update_drill(&mut world.resource_mut::<SelectedDrill>().0, field, value);
+        }
+    });
+}
+
+#[derive(Clone)]
+pub struct MenuSystemsPlugin {
+    install_egui: bool,
+}
+
+impl Default for MenuSystemsPlugin {
+    fn default() -> Self {
+        Self { install_egui: true }
+    }
+}
+
+impl MenuSystemsPlugin {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn without_egui(mut self) -> Self {
+        self.install_egui = false;
+        self
+    }
+}
 
 impl Plugin for MenuSystemsPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(EguiPlugin)
-            .add_systems(OnEnter(GameState::Controls), init_controls_menu)
-            .add_systems(OnExit(GameState::Controls), destroy_controls_menu)
-            .add_systems(OnEnter(GameState::Startgame), init_start_game_menu)
+        crate::add_egui_if_missing(app, self.install_egui);
+        if !app.is_plugin_added::<LocalizationPlugin>() {
+            app.add_plugins(LocalizationPlugin);
+        }
+        app.add_plugins((MenuDefinitionPlugin, MenuNavigationPlugin));
+        register_menu_actions(app);
+
+        app
+            .add_systems(OnEnter(GameState::Main), reset_menu_stack(MenuScreen::Main))
+            .add_systems(OnEnter(GameState::Playing), clear_menu_stack)
+            .add_systems(OnEnter(GameState::Endgame), reset_menu_stack(MenuScreen::Endgame))
+            .add_systems(OnEnter(MenuScreen::Controls), init_controls_menu)
+            .add_systems(OnExit(MenuScreen::Controls), destroy_controls_menu)
+            .add_systems(OnEnter(MenuScreen::Profiles), init_profiles_menu)
+            .add_systems(OnExit(MenuScreen::Profiles), destroy_profiles_menu)
             .add_systems(Update, (
-                start_game_menu.in_set(StartGameSet),
-                main_menu.in_set(MainSet),
-                (controls_menu, listen_for_keys).in_set(ControlsSet),
-                toggle_pause_game.in_set(PlayingSet),
-                (toggle_pause_game, paused_menu).in_set(PausedSet),
-                end_game_menu.in_set(EndgameSet),
+                navigate_back,
+                auto_pause.run_if(in_state(GameState::Playing)),
+                sync_paused_state
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(resource_changed::<MenuStack>),
+                show_menu_definition("menus/start_game.menu.ron").in_set(StartGameSet),
+                show_menu_definition("menus/main.menu.ron").in_set(MainSet),
+                (show_menu_definition("menus/controls.menu.ron"), listen_for_keys).in_set(ControlsSet),
+                show_menu_definition("menus/paused.menu.ron").in_set(PausedSet),
+                show_menu_definition("menus/settings.menu.ron").in_set(SettingsSet),
+                show_menu_definition("menus/rules.menu.ron").in_set(RulesSet),
+                show_menu_definition("menus/profiles.menu.ron").in_set(ProfilesSet),
+                show_menu_definition("menus/training.menu.ron").in_set(TrainingSet),
+                show_menu_definition("menus/endgame.menu.ron").in_set(EndgameSet),
+                confirmation_dialog.run_if(resource_exists::<MenuConfirmation>),
             ));
     }
 }
\ No newline at end of file
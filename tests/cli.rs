@@ -0,0 +1,55 @@
+use pong::cli::{ArenaPreset, LaunchOptions};
+use pong::game::settings::{Difficulty, PlayerType};
+use std::process::Command;
+
+fn parse(args: &str) -> Result<LaunchOptions, String> {
+    LaunchOptions::parse(args.split_whitespace().map(String::from))
+}
+
+#[test]
+fn no_options_opens_the_menu() {
+    let options = parse("").unwrap();
+    assert!(!options.skips_menu());
+}
+
+#[test]
+fn parses_a_headless_run() {
+    let options = parse("--p1 human --p2 ai:impossible --target 11 --seed 42 --arena classic").unwrap();
+    assert_eq!(options.player1, Some(PlayerType::Human));
+    assert_eq!(options.player2, Some(PlayerType::Computer(Difficulty::Impossible)));
+    assert_eq!(options.target_score, Some(11));
+    assert_eq!(options.seed, Some(42));
+    assert_eq!(options.arena, Some(ArenaPreset::Classic));
+    assert!(options.skips_menu());
+
+    let options = parse("--p1 ai:easy --p2 ai:difficult --headless --matches 100 --replay-out out/").unwrap();
+    assert!(options.headless);
+    assert_eq!(options.match_count(), 100);
+    assert_eq!(options.replay_out, Some("out/".into()));
+}
+
+#[test]
+fn rejects_invalid_options() {
+    assert!(parse("--p1 robot").is_err());
+    assert!(parse("--target").is_err());
+    assert!(parse("--target 0").is_err());
+    assert!(parse("--arena huge").is_err());
+    assert!(parse("--fullscreen").is_err());
+    assert!(parse("--matches 10").is_err(), "matches without headless");
+    assert!(parse("--headless --p2 ai:easy").is_err(), "headless with a human player");
+}
+
+#[test]
+fn stalled_headless_match_has_no_winner() {
+    let output = Command::new(env!("CARGO_BIN_EXE_pong"))
+        .args(["--headless", "--p1", "ai:impossible", "--p2", "ai:impossible", "--target", "1000", "--seed", "1"])
+        .output()
+        .expect("pong should run");
+    assert!(output.status.success());
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("report should be JSON");
+    assert_eq!(report["wins"], serde_json::json!([0, 0]));
+    assert_eq!(report["unfinished"], 1);
+    assert_eq!(report["matches"][0]["winner"], serde_json::Value::Null);
+    assert_eq!(report["matches"][0]["completed"], false);
+}
\ No newline at end of file
@@ -0,0 +1,164 @@
+#![allow(dead_code)]
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::Velocity;
+use leafwing_input_manager::prelude::*;
+
+use pong::game::controls::GameAction;
+use pong::game::settings::{GameMode, GameSettings, PlayerType};
+use pong::game::states::{GameState, MatchPointState, MatchState};
+use pong::pong::{Ball, Score, ScoreField, StateHash};
+use pong::{GamePlugin, HeadlessPlugin, PongPlugin};
+
+pub const ARENA_WIDTH: f32 = 1280.;
+pub const ARENA_HEIGHT: f32 = 720.;
+
+/// Ticks needed for the "3, 2, 1, Go!" countdown, with some slack.
+pub const COUNTDOWN_TICKS: u32 = 4 * 64;
+
+/// Headless game where every `tick` advances time by exactly one fixed timestep.
+pub struct TestGame {
+    pub app: App,
+}
+
+impl TestGame {
+    pub fn new() -> Self {
+        Self::with_pong(PongPlugin::new())
+    }
+
+    pub fn seeded(seed: u64) -> Self {
+        Self::with_pong(PongPlugin::new().with_seed(seed))
+    }
+
+    fn with_pong(pong: PongPlugin) -> Self {
+        let mut app = App::new();
+        app.add_plugins((
+            HeadlessPlugin::new(),
+            GamePlugin::new().without_storage(),
+            pong.with_arena_size(ARENA_WIDTH, ARENA_HEIGHT),
+        ));
+        app.update();
+
+        Self { app }
+    }
+
+    pub fn start_match(&mut self, player1: PlayerType, player2: PlayerType) {
+        self.start_mode(GameMode::Versus, player1, player2);
+    }
+
+    pub fn start_mode(&mut self, mode: GameMode, player1: PlayerType, player2: PlayerType) {
+        let mut settings = self.app.world_mut().resource_mut::<GameSettings>();
+        settings.set_mode(mode);
+        settings.update_players(1, player1);
+        settings.update_players(2, player2);
+
+        self.app.world_mut().resource_mut::<NextState<GameState>>().set(GameState::Playing);
+        self.tick();
+    }
+
+    pub fn tick(&mut self) {
+        self.app.update();
+    }
+
+    pub fn ticks(&mut self, count: u32) {
+        for _ in 0..count {
+            self.tick();
+        }
+    }
+
+    /// Ticks until `condition` holds, panicking after `max_ticks`.
+    pub fn run_until(&mut self, max_ticks: u32, condition: impl Fn(&mut Self) -> bool) {
+        for _ in 0..max_ticks {
+            if condition(self) {
+                return;
+            }
+            self.tick();
+        }
+        assert!(condition(self), "condition not met within {} ticks", max_ticks);
+    }
+
+    /// Simulates the bound input events, so presses go through the `InputMap`.
+    pub fn press(&mut self, action: GameAction) {
+        for binding in self.bindings(action) {
+            binding.press(self.app.world_mut());
+        }
+    }
+
+    pub fn release(&mut self, action: GameAction) {
+        for binding in self.bindings(action) {
+            binding.release(self.app.world_mut());
+        }
+    }
+
+    /// Holds `actions` down for `ticks` ticks, then releases them.
+    pub fn hold(&mut self, actions: &[GameAction], ticks: u32) {
+        actions.iter().for_each(|action| self.press(*action));
+        self.ticks(ticks);
+        actions.iter().for_each(|action| self.release(*action));
+    }
+
+    fn bindings(&self, action: GameAction) -> Vec<Box<dyn Buttonlike>> {
+        self.app.world().resource::<InputMap<GameAction>>()
+            .get_buttonlike(&action)
+            .cloned()
+            .unwrap_or_else(|| panic!("{:?} has no binding", action))
+    }
+
+    pub fn state_hash(&self) -> StateHash {
+        *self.app.world().resource::<StateHash>()
+    }
+
+    /// Ticks `count` times and collects the state hash after every tick.
+    pub fn hashes(&mut self, count: u32) -> Vec<u64> {
+        (0..count).map(|_| {
+            self.tick();
+            self.state_hash().value
+        }).collect()
+    }
+
+    pub fn score(&self) -> (u32, u32) {
+        self.app.world().resource::<Score>().points()
+    }
+
+    pub fn game_state(&self) -> GameState {
+        self.app.world().resource::<State<GameState>>().get().clone()
+    }
+
+    pub fn match_state(&self) -> Option<MatchState> {
+        self.app.world().get_resource::<State<MatchState>>().map(|state| *state.get())
+    }
+
+    pub fn is_match_point(&self) -> bool {
+        self.app.world().get_resource::<State<MatchPointState>>()
+            .is_some_and(|state| *state.get() == MatchPointState::Active)
+    }
+
+    pub fn ball(&mut self) -> (Vec2, Vec2) {
+        let mut balls = self.app.world_mut().query_filtered::<(&Transform, &Velocity), With<Ball>>();
+        let (transform, velocity) = balls.single(self.app.world());
+        (transform.translation.truncate(), velocity.linvel)
+    }
+
+    /// Teleports the ball, e.g. to serve it at a paddle.
+    pub fn place_ball(&mut self, position: Vec2, velocity: Vec2) {
+        let mut balls = self.app.world_mut().query_filtered::<(&mut Transform, &mut Velocity), With<Ball>>();
+        let (mut transform, mut ball_velocity) = balls.single_mut(self.app.world_mut());
+        transform.translation = position.extend(0.);
+        ball_velocity.linvel = velocity;
+        ball_velocity.angvel = 0.;
+    }
+
+    pub fn paddle(&mut self, field: ScoreField) -> Vec2 {
+        let mut paddles = self.app.world_mut().query_filtered::<(&Transform, &ScoreField), With<PlayerType>>();
+        paddles.iter(self.app.world())
+            .find(|(_, paddle_field)| **paddle_field == field)
+            .map(|(transform, _)| transform.translation.truncate())
+            .expect("paddle should exist")
+    }
+
+    /// Starts a match and ticks through the serve countdown.
+    pub fn start_rally(&mut self, player1: PlayerType, player2: PlayerType) {
+        self.start_match(player1, player2);
+        self.run_until(COUNTDOWN_TICKS, |game| game.match_state() == Some(MatchState::Rally));
+    }
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use bevy::prelude::*;
+use common::TestGame;
+use pong::console::ConsoleCommand;
+use pong::game::commands::{ChangeGameStateCommand, UpdatePlayerCommand};
+use pong::game::settings::{Difficulty, GameSettings, PlayerType};
+use pong::game::states::GameState;
+use pong::pong::commands::{ResetBallCommand, SetScoreCommand, SetTargetScoreCommand};
+use pong::pong::Rules;
+
+fn parse(line: &str) -> Result<ConsoleCommand, String> {
+    line.parse()
+}
+
+#[test]
+fn parses_every_command() {
+    assert_eq!(parse("score 3 4"), Ok(ConsoleCommand::Score(SetScoreCommand::new(3, 4))));
+    assert_eq!(parse("ball reset"), Ok(ConsoleCommand::BallReset(ResetBallCommand)));
+    assert_eq!(
+        parse("difficulty 2 impossible"),
+        Ok(ConsoleCommand::Difficulty(UpdatePlayerCommand::new(2, PlayerType::Computer(Difficulty::Impossible)))),
+    );
+    assert_eq!(parse("  state   endgame "), Ok(ConsoleCommand::State(ChangeGameStateCommand::new(GameState::Endgame))));
+    assert_eq!(parse("rules target 7"), Ok(ConsoleCommand::RulesTarget(SetTargetScoreCommand::new(7))));
+    assert!(matches!(parse("ball speed 500"), Ok(ConsoleCommand::BallSpeed(_))));
+    assert!(matches!(parse("timescale 0.5"), Ok(ConsoleCommand::TimeScale(_))));
+}
+
+#[test]
+fn rejects_malformed_commands() {
+    assert_eq!(parse(""), Err("Empty command".to_string()));
+    assert_eq!(parse("score 3"), Err("Missing score".to_string()));
+    assert_eq!(parse("score three 4"), Err("Invalid score \"three\"".to_string()));
+    assert_eq!(parse("score 1 2 3"), Err("Unexpected argument \"3\"".to_string()));
+    assert_eq!(parse("difficulty 3 easy"), Err("Invalid player 3".to_string()));
+    assert_eq!(parse("state paused"), Err("Unknown state \"paused\"".to_string()));
+    assert!(parse("ball spawn").is_err());
+    assert!(parse("jump").is_err());
+}
+
+#[test]
+fn commands_change_the_running_game() {
+    let mut game = TestGame::new();
+    game.start_rally(PlayerType::Human, PlayerType::Human);
+
+    for line in ["score 2 1", "rules target 9", "difficulty 1 easy"] {
+        parse(line).unwrap().apply(game.app.world_mut());
+    }
+    game.tick();
+
+    assert_eq!(game.score(), (2, 1));
+    assert_eq!(game.app.world().resource::<Rules>().target_score, 9);
+    assert_eq!(
+        *game.app.world().resource::<GameSettings>().get_player1(),
+        PlayerType::Computer(Difficulty::Easy),
+    );
+}
+
+#[test]
+fn ball_reset_puts_the_ball_back_in_the_centre() {
+    let mut game = TestGame::new();
+    game.start_rally(PlayerType::Human, PlayerType::Human);
+    game.place_ball(Vec2::new(300., 100.), Vec2::new(400., 0.));
+
+    parse("ball reset").unwrap().apply(game.app.world_mut());
+    game.tick();
+
+    assert!(game.ball().0.length() < 50., "ball was not reset");
+    assert_eq!(game.app.world_mut().query::<&pong::pong::Ball>().iter(game.app.world()).count(), 1);
+}
\ No newline at end of file
@@ -0,0 +1,48 @@
+use bevy::prelude::*;
+use leafwing_input_manager::prelude::*;
+use pong::game::commands::SwapSidesCommand;
+use pong::game::controls::GameAction;
+use pong::game::settings::GameSettings;
+
+fn keys(input_map: &InputMap<GameAction>, action: GameAction) -> Vec<String> {
+    input_map.get(&action)
+        .unwrap_or_default()
+        .iter()
+        .map(|input| format!("{:?}", input))
+        .collect()
+}
+
+#[test]
+fn swapping_sides_swaps_every_binding() {
+    let mut world = World::new();
+    world.init_resource::<GameSettings>();
+    world.insert_resource(GameAction::default_input_map());
+
+    let before = world.resource::<InputMap<GameAction>>().clone();
+    SwapSidesCommand.apply(&mut world);
+    let after = world.resource::<InputMap<GameAction>>();
+
+    assert_eq!(keys(after, GameAction::Player1Up), keys(&before, GameAction::Player2Up));
+    assert_eq!(keys(after, GameAction::Player2Down), keys(&before, GameAction::Player1Down));
+}
+
+#[test]
+fn swapping_sides_moves_an_unbound_action_too() {
+    let mut world = World::new();
+    world.init_resource::<GameSettings>();
+    let mut input_map = GameAction::default_input_map();
+    input_map.clear_action(&GameAction::Player2Up);
+    world.insert_resource(input_map);
+
+    SwapSidesCommand.apply(&mut world);
+    let input_map = world.resource::<InputMap<GameAction>>();
+
+    assert!(input_map.get(&GameAction::Player1Up).is_none());
+    assert_eq!(keys(input_map, GameAction::Player2Up), keys(&GameAction::default_input_map(), GameAction::Player1Up));
+
+    SwapSidesCommand.apply(&mut world);
+    let input_map = world.resource::<InputMap<GameAction>>();
+
+    assert!(input_map.get(&GameAction::Player2Up).is_none());
+    assert_eq!(keys(input_map, GameAction::Player1Up), keys(&GameAction::default_input_map(), GameAction::Player1Up));
+}
\ No newline at end of file
@@ -0,0 +1,67 @@
+mod common;
+
+use common::{TestGame, COUNTDOWN_TICKS};
+use pong::game::controls::GameAction;
+use pong::game::settings::{Difficulty, GameMode, PlayerType};
+use pong::game::states::MatchState;
+
+const TICKS: u32 = 20 * 64;
+
+fn versus_run(seed: u64) -> Vec<u64> {
+    let mut game = TestGame::seeded(seed);
+    game.start_match(PlayerType::Human, PlayerType::Computer(Difficulty::Impossible));
+
+    let mut hashes = game.hashes(COUNTDOWN_TICKS);
+    game.press(GameAction::Player1Up);
+    hashes.extend(game.hashes(30));
+    game.release(GameAction::Player1Up);
+    hashes.extend(game.hashes(TICKS));
+    hashes
+}
+
+fn training_run(seed: u64) -> Vec<u64> {
+    let mut game = TestGame::seeded(seed);
+    game.start_mode(GameMode::Training, PlayerType::Computer(Difficulty::Easy), PlayerType::Human);
+    game.hashes(TICKS)
+}
+
+#[test]
+fn same_inputs_give_identical_ticks() {
+    let first = versus_run(42);
+    let second = versus_run(42);
+
+    assert_eq!(first.len(), second.len());
+    for (tick, (first, second)) in first.iter().zip(&second).enumerate() {
+        assert_eq!(first, second, "runs diverged at tick {}", tick);
+    }
+}
+
+#[test]
+fn seed_drives_training_shots() {
+    assert_eq!(training_run(7), training_run(7));
+    assert_ne!(training_run(7), training_run(8));
+}
+
+#[test]
+fn hash_changes_only_with_the_state() {
+    let mut game = TestGame::seeded(1);
+    game.start_match(PlayerType::Human, PlayerType::Human);
+    game.tick();
+
+    // Without input nothing moves during the countdown
+    let before = game.state_hash();
+    game.tick();
+    let after = game.state_hash();
+    assert_eq!(after.tick, before.tick + 1);
+    assert_eq!(after.value, before.value);
+
+    game.hold(&[GameAction::Player1Up], 5);
+    let moved = game.state_hash();
+    assert_eq!(game.match_state(), Some(MatchState::Serving));
+    assert_ne!(moved.value, after.value, "moving paddle did not change the hash");
+
+    game.run_until(COUNTDOWN_TICKS, |game| game.match_state() == Some(MatchState::Rally));
+    let serve = game.state_hash();
+    game.tick();
+    assert_ne!(game.state_hash().value, serve.value, "moving ball did not change the hash");
+}
\ No newline at end of file
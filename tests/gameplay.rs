@@ -0,0 +1,94 @@
+mod common;
+
+use bevy::prelude::*;
+use common::TestGame;
+use pong::game::controls::GameAction;
+use pong::game::states::MatchState;
+use pong::game::settings::{Difficulty, GameMode, PlayerType};
+use pong::pong::ScoreField;
+
+const PADDLE_HEIGHT: f32 = 100.;
+
+#[test]
+fn ball_served_at_paddle_centre_returns_straight() {
+    let mut game = TestGame::new();
+    game.start_rally(PlayerType::Human, PlayerType::Computer(Difficulty::Easy));
+
+    let paddle = game.paddle(ScoreField::Left);
+    game.place_ball(Vec2::new(paddle.x + 200., paddle.y), Vec2::new(-400., 0.));
+    game.run_until(64, |game| game.ball().1.x > 0.);
+
+    let (position, velocity) = game.ball();
+    assert!((position.y - paddle.y).abs() < 1., "ball drifted to {}", position.y);
+    assert!(velocity.y.abs() < 1., "ball returned at an angle: {:?}", velocity);
+}
+
+#[test]
+fn ball_served_at_paddle_edge_returns_at_an_angle() {
+    let mut game = TestGame::new();
+    game.start_rally(PlayerType::Human, PlayerType::Computer(Difficulty::Easy));
+
+    let paddle = game.paddle(ScoreField::Left);
+    game.place_ball(Vec2::new(paddle.x + 200., paddle.y + PADDLE_HEIGHT / 3.), Vec2::new(-400., 0.));
+    game.run_until(64, |game| game.ball().1.x > 0.);
+
+    assert!(game.ball().1.y > 0., "ball should leave upwards");
+}
+
+#[test]
+fn scripted_input_moves_paddle() {
+    let mut game = TestGame::new();
+    game.start_rally(PlayerType::Human, PlayerType::Computer(Difficulty::Easy));
+    let start = game.paddle(ScoreField::Left);
+
+    game.hold(&[GameAction::Player1Up], 10);
+    let raised = game.paddle(ScoreField::Left);
+    assert!(raised.y > start.y, "paddle did not move up");
+
+    game.hold(&[GameAction::Player1Down], 20);
+    assert!(game.paddle(ScoreField::Left).y < raised.y, "paddle did not move down");
+    assert_eq!(game.paddle(ScoreField::Left).x, start.x);
+}
+
+#[test]
+fn paddles_move_during_the_serve_countdown() {
+    let mut game = TestGame::new();
+    game.start_match(PlayerType::Human, PlayerType::Human);
+    game.tick();
+    assert_eq!(game.match_state(), Some(MatchState::Serving));
+    let start = game.paddle(ScoreField::Left);
+    let (ball, _) = game.ball();
+
+    game.hold(&[GameAction::Player1Up], 10);
+    assert_eq!(game.match_state(), Some(MatchState::Serving));
+    assert!(game.paddle(ScoreField::Left).y > start.y, "paddle ignored input during the countdown");
+    assert_eq!(game.ball().0, ball, "ball moved before the serve");
+}
+
+#[test]
+fn practice_paddle_is_always_human() {
+    let mut game = TestGame::new();
+    game.start_mode(GameMode::Practice, PlayerType::Computer(Difficulty::Impossible), PlayerType::Human);
+    game.run_until(common::COUNTDOWN_TICKS, |game| game.match_state() == Some(MatchState::Rally));
+    let start = game.paddle(ScoreField::Left);
+
+    game.hold(&[GameAction::Player1Up], 10);
+    assert!(game.paddle(ScoreField::Left).y > start.y, "paddle ignored player input");
+}
+
+#[test]
+fn paddles_stay_inside_the_arena() {
+    let mut game = TestGame::new();
+    game.start_rally(PlayerType::Human, PlayerType::Human);
+
+    let top = common::ARENA_HEIGHT / 2. - 100.;
+    let bottom = -common::ARENA_HEIGHT / 2.;
+
+    game.hold(&[GameAction::Player1Up, GameAction::Player2Down], 200);
+    assert!(game.paddle(ScoreField::Left).y + PADDLE_HEIGHT / 2. <= top);
+    assert!(game.paddle(ScoreField::Right).y - PADDLE_HEIGHT / 2. >= bottom);
+
+    game.hold(&[GameAction::Player1Down, GameAction::Player2Up], 200);
+    assert!(game.paddle(ScoreField::Left).y - PADDLE_HEIGHT / 2. >= bottom);
+    assert!(game.paddle(ScoreField::Right).y + PADDLE_HEIGHT / 2. <= top);
+}
@@ -0,0 +1,174 @@
+mod common;
+
+use bevy::prelude::*;
+use common::{TestGame, COUNTDOWN_TICKS};
+use pong::game::profiles::{PlayerProfile, PlayerProfiles};
+use pong::game::settings::{Difficulty, GameSettings, PlayerType};
+use pong::game::states::{GameState, MatchState};
+use pong::pong::{CountdownDisplay, Series};
+use pong::pong::commands::SetScoreCommand;
+use pong::pong::events::{MatchEnded, MatchPointReached};
+
+#[derive(Resource, Default)]
+struct MatchPoints(Vec<(usize, (u32, u32))>);
+
+#[derive(Resource, Default)]
+struct Winners(Vec<Option<usize>>);
+
+#[test]
+fn countdown_starts_the_rally() {
+    let mut game = TestGame::new();
+    game.start_match(PlayerType::Human, PlayerType::Human);
+
+    assert_eq!(game.game_state(), GameState::Playing);
+    assert_eq!(game.match_state(), Some(MatchState::Serving));
+
+    game.ticks(64);
+    assert_eq!(game.match_state(), Some(MatchState::Serving), "rally started during the countdown");
+
+    game.run_until(COUNTDOWN_TICKS, |game| game.match_state() == Some(MatchState::Rally));
+    assert_eq!(game.score(), (0, 0));
+}
+
+#[test]
+fn headless_countdown_is_localized() {
+    let mut game = TestGame::new();
+    game.start_rally(PlayerType::Human, PlayerType::Human);
+
+    let mut display = game.app.world_mut().query_filtered::<&Text2d, With<CountdownDisplay>>();
+    assert_eq!(display.single(game.app.world()).0, "Go!");
+}
+
+#[test]
+fn ball_into_goal_scores_a_point() {
+    let mut game = TestGame::new();
+    game.start_rally(PlayerType::Human, PlayerType::Human);
+
+    game.place_ball(Vec2::new(-500., 200.), Vec2::new(-800., 0.));
+    game.run_until(64, |game| game.score() != (0, 0));
+
+    assert_eq!(game.score(), (0, 1));
+}
+
+#[test]
+fn five_goals_transitions_to_endgame() {
+    let mut game = TestGame::new();
+    game.start_rally(PlayerType::Human, PlayerType::Computer(Difficulty::Easy));
+
+    for point in 1..=5 {
+        game.run_until(COUNTDOWN_TICKS, |game| game.match_state() == Some(MatchState::Rally));
+        assert_eq!(game.game_state(), GameState::Playing);
+
+        // Far from the computer paddle, which rests at the centre
+        game.place_ball(Vec2::new(500., 250.), Vec2::new(800., 0.));
+        game.run_until(64, |game| game.score().0 == point);
+        game.run_until(4, |game| game.match_state() != Some(MatchState::Rally));
+    }
+
+    game.run_until(4, |game| game.game_state() == GameState::Endgame);
+    assert_eq!(game.score(), (5, 0));
+    assert_eq!(game.match_state(), None);
+}
+
+#[test]
+fn match_point_holds_through_the_serve_and_rally() {
+    let mut game = TestGame::new();
+    game.start_rally(PlayerType::Human, PlayerType::Computer(Difficulty::Easy));
+
+    for point in 1..=4 {
+        assert!(!game.is_match_point(), "match point before point {}", point);
+
+        game.place_ball(Vec2::new(500., 250.), Vec2::new(800., 0.));
+        game.run_until(64, |game| game.score().0 == point);
+        game.run_until(4, |game| game.match_state() != Some(MatchState::Rally));
+        game.run_until(COUNTDOWN_TICKS, |game| game.match_state() == Some(MatchState::Rally));
+    }
+
+    assert_eq!(game.score(), (4, 0));
+    assert!(game.is_match_point());
+    game.ticks(8);
+    assert_eq!(game.match_state(), Some(MatchState::Rally));
+    assert!(game.is_match_point());
+}
+
+#[test]
+fn match_point_is_published_once_per_player() {
+    let mut game = TestGame::new();
+    game.app.init_resource::<MatchPoints>()
+        .add_observer(|trigger: Trigger<MatchPointReached>, mut seen: ResMut<MatchPoints>| {
+            seen.0.push((trigger.player, trigger.score));
+        });
+    game.start_rally(PlayerType::Human, PlayerType::Human);
+    SetScoreCommand::new(4, 0).apply(game.app.world_mut());
+
+    for point in 1..=4 {
+        game.place_ball(Vec2::new(-500., 250.), Vec2::new(-800., 0.));
+        game.run_until(64, |game| game.score().1 == point);
+        game.run_until(4, |game| game.match_state() != Some(MatchState::Rally));
+        game.run_until(COUNTDOWN_TICKS, |game| game.match_state() == Some(MatchState::Rally));
+    }
+
+    assert_eq!(game.score(), (4, 4));
+    assert_eq!(game.app.world().resource::<MatchPoints>().0, vec![(1, (4, 1)), (2, (4, 4))]);
+}
+
+#[test]
+fn tied_forced_ending_has_no_winner() {
+    let mut game = TestGame::new();
+    game.app.init_resource::<Winners>()
+        .add_observer(|trigger: Trigger<MatchEnded>, mut seen: ResMut<Winners>| {
+            seen.0.push(trigger.winner);
+        });
+    game.start_rally(PlayerType::Human, PlayerType::Human);
+    SetScoreCommand::new(2, 2).apply(game.app.world_mut());
+
+    game.app.world_mut().resource_mut::<NextState<GameState>>().set(GameState::Endgame);
+    game.ticks(2);
+
+    assert_eq!(game.game_state(), GameState::Endgame);
+    assert_eq!(game.app.world().resource::<Winners>().0, vec![None]);
+}
+
+fn profile_wins_after_forced_ending(player1: u32, player2: u32) -> (u32, u32) {
+    let mut game = TestGame::new();
+    let world = game.app.world_mut();
+    for (player_num, name) in [(1, "Ann"), (2, "Bob")] {
+        world.resource_mut::<PlayerProfiles>().add(PlayerProfile::new(name, Color::WHITE));
+        world.resource_mut::<GameSettings>().update_profile(player_num, Some(name.to_string()));
+    }
+    force_ending(&mut game, player1, player2);
+
+    let profiles = game.app.world().resource::<PlayerProfiles>();
+    let stats = |name| profiles.get(name).expect("profile should exist").stats;
+    assert_eq!(stats("Bob").matches_played, 1);
+    (stats("Ann").matches_won, stats("Bob").matches_won)
+}
+
+#[test]
+fn tied_forced_ending_is_not_a_profile_win() {
+    assert_eq!(profile_wins_after_forced_ending(2, 2), (0, 0));
+    assert_eq!(profile_wins_after_forced_ending(1, 2), (0, 1));
+}
+
+fn force_ending(game: &mut TestGame, player1: u32, player2: u32) {
+    game.start_rally(PlayerType::Human, PlayerType::Human);
+    SetScoreCommand::new(player1, player2).apply(game.app.world_mut());
+    game.app.world_mut().resource_mut::<NextState<GameState>>().set(GameState::Endgame);
+    game.ticks(2);
+}
+
+#[test]
+fn tied_forced_ending_is_not_a_series_game() {
+    let mut game = TestGame::new();
+    game.app.world_mut().resource_mut::<GameSettings>().set_series_length(3);
+
+    force_ending(&mut game, 2, 2);
+    let series = game.app.world().resource::<Series>();
+    assert_eq!(series.wins(), (0, 0));
+    assert_eq!(series.get_winner_num(), None);
+
+    force_ending(&mut game, 3, 1);
+    let series = game.app.world().resource::<Series>();
+    assert_eq!(series.wins(), (1, 0));
+    assert_eq!(series.get_winner_num(), Some(1));
+}
\ No newline at end of file
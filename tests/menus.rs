@@ -0,0 +1,43 @@
+use pong::ui::menu::definition::{ComponentDefinition, MenuDefinition, TextDefinition};
+use std::fs;
+
+fn load(name: &str) -> MenuDefinition {
+    let path = format!("{}/assets/menus/{}.menu.ron", env!("CARGO_MANIFEST_DIR"), name);
+    let contents = fs::read_to_string(&path).unwrap();
+    ron::de::from_str(&contents).unwrap_or_else(|error| panic!("{}: {}", path, error))
+}
+
+#[test]
+fn every_menu_definition_parses() {
+    for entry in fs::read_dir(format!("{}/assets/menus", env!("CARGO_MANIFEST_DIR"))).unwrap() {
+        let file_name = entry.unwrap().file_name().into_string().unwrap();
+        let menu = load(file_name.trim_end_matches(".menu.ron"));
+        assert!(!menu.components.is_empty(), "{} has no components", file_name);
+    }
+}
+
+#[test]
+fn headings_and_labels_accept_keys_or_dynamic_text() {
+    let main = load("main");
+    assert!(matches!(&main.heading, TextDefinition::Key(key) if key == "main.title"));
+
+    let endgame = load("endgame");
+    assert!(matches!(&endgame.heading, TextDefinition::Dynamic { source, .. } if source == "match_winner"));
+    assert!(matches!(
+        &endgame.components[0],
+        ComponentDefinition::When { component, .. }
+            if matches!(component.as_ref(), ComponentDefinition::Label(TextDefinition::Dynamic { .. }))
+    ));
+}
+
+#[test]
+fn settings_use_value_widgets() {
+    let settings = load("settings");
+    let dropdowns = settings.components.iter()
+        .filter(|component| matches!(component, ComponentDefinition::Dropdown { .. }))
+        .count();
+
+    assert_eq!(dropdowns, 4);
+    assert!(settings.components.iter().any(|component| matches!(component, ComponentDefinition::Checkbox { .. })));
+    assert!(load("training").components.iter().any(|component| matches!(component, ComponentDefinition::Slider { .. })));
+}
\ No newline at end of file
@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+use pong::game::settings::Difficulty;
+use pong::simulation::{simulate, to_csv, to_json, BatchOptions, PairingReport};
+
+fn report() -> PairingReport {
+    PairingReport {
+        player1: Difficulty::Easy,
+        player2: Difficulty::Impossible,
+        matches: 4,
+        wins: (1, 2),
+        unfinished: 1,
+        points: 10,
+        stalled_points: 3,
+        rally_hits: 25,
+        point_seconds: 42.,
+    }
+}
+
+#[test]
+fn report_averages() {
+    let report = report();
+    assert_eq!(report.win_rate(1), 0.25);
+    assert_eq!(report.win_rate(2), 0.5);
+    assert_eq!(report.average_rally_length(), 2.5);
+    assert_eq!(report.average_point_duration(), 4.2);
+    assert_eq!(PairingReport::default().average_rally_length(), 0.);
+}
+
+#[test]
+fn csv_has_a_row_per_pairing() {
+    let csv = to_csv(&[report(), report()]);
+    let lines: Vec<&str> = csv.lines().collect();
+
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("player1,player2,matches"));
+    assert_eq!(lines[1], "easy,impossible,4,1,2,1,0.250,0.500,2.50,4.20,3");
+}
+
+#[test]
+fn json_names_the_difficulties() {
+    let json = to_json(&[report()]);
+    assert!(json.contains("\"player1\": \"easy\""));
+    assert!(json.contains("\"player2_wins\": 2"));
+}
+
+#[test]
+fn harder_computers_win_more_often() {
+    let options = BatchOptions {
+        matches: 4,
+        target_score: 2,
+        arena_size: Vec2::new(1280., 720.),
+        seed: 3,
+    };
+
+    for (weaker, stronger) in [
+        (Difficulty::Easy, Difficulty::Difficult),
+        (Difficulty::Difficult, Difficulty::Impossible),
+    ] {
+        let report = simulate(weaker, stronger, options);
+        assert!(
+            report.win_rate(2) > report.win_rate(1),
+            "{:?} did not beat {:?}: {:?}", report.player2, report.player1, report.wins,
+        );
+    }
+}
\ No newline at end of file
@@ -0,0 +1,118 @@
+mod common;
+
+use common::TestGame;
+use pong::game::settings::{Difficulty, PlayerType};
+use pong::spectator::{SpectatorAddress, SpectatorPlugin, StateFrame, MAX_SPECTATORS, SCHEMA_VERSION};
+use std::io::Write;
+use std::net::TcpStream;
+use std::thread;
+use std::time::Duration;
+use tungstenite::stream::MaybeTlsStream;
+use tungstenite::{Message, WebSocket};
+
+fn connect(game: &mut TestGame, decimation: u32) -> WebSocket<MaybeTlsStream<TcpStream>> {
+    game.app.add_plugins(SpectatorPlugin::new()
+        .with_address(([127, 0, 0, 1], 0).into())
+        .with_decimation(decimation));
+    join(game)
+}
+
+/// Connects another client to the server started by `connect`.
+fn join(game: &TestGame) -> WebSocket<MaybeTlsStream<TcpStream>> {
+    let address = game.app.world().resource::<SpectatorAddress>().0;
+    let (socket, _) = tungstenite::connect(format!("ws://{}", address)).expect("spectator server should accept");
+    if let MaybeTlsStream::Plain(stream) = socket.get_ref() {
+        stream.set_read_timeout(Some(Duration::from_millis(20))).unwrap();
+    }
+    socket
+}
+
+/// Ticks the game until the client has received `count` frames.
+fn frames(game: &mut TestGame, socket: &mut WebSocket<MaybeTlsStream<TcpStream>>, count: usize) -> Vec<StateFrame> {
+    let mut frames = Vec::new();
+    for _ in 0..1000 {
+        game.tick();
+        while let Ok(Message::Text(text)) = socket.read() {
+            frames.push(serde_json::from_str(&text).expect("frames should match the schema"));
+        }
+        if frames.len() >= count {
+            return frames;
+        }
+    }
+    panic!("received {} of {} frames", frames.len(), count);
+}
+
+#[test]
+fn spectators_receive_the_match_state() {
+    let mut game = TestGame::new();
+    let mut socket = connect(&mut game, 1);
+    game.start_rally(PlayerType::Human, PlayerType::Computer(Difficulty::Impossible));
+
+    let frame = frames(&mut game, &mut socket, 1).pop().unwrap();
+    assert_eq!(frame.version, SCHEMA_VERSION);
+    assert_eq!(frame.game_state, "Playing");
+    assert_eq!(frame.match_state.as_deref(), Some("Rally"));
+    assert_eq!(frame.score, game.score());
+
+    let (position, velocity) = game.ball();
+    let ball = frame.ball.expect("ball should be in play");
+    // The latest frame can trail the world by a tick
+    assert!((ball.x - position.x).abs() < 50.);
+    assert_eq!(ball.vx.signum(), velocity.x.signum());
+
+    assert_eq!(frame.players.len(), 2);
+    assert_eq!(frame.players[0].name, "Player 1");
+    assert_eq!(frame.players[0].kind, "human");
+    assert_eq!(frame.players[1].kind, "ai:impossible");
+    assert!(frame.players.iter().all(|player| player.paddle.is_some()));
+}
+
+#[test]
+fn decimation_skips_ticks() {
+    let mut game = TestGame::new();
+    let mut socket = connect(&mut game, 4);
+    game.start_match(PlayerType::Human, PlayerType::Human);
+
+    let ticks: Vec<u64> = frames(&mut game, &mut socket, 5).iter().map(|frame| frame.tick).collect();
+    assert!(ticks.iter().all(|tick| tick % 4 == 0), "unexpected ticks {:?}", ticks);
+    assert!(ticks.windows(2).all(|pair| pair[1] - pair[0] == 4), "frames were dropped: {:?}", ticks);
+}
+
+#[test]
+fn stalled_clients_do_not_hold_up_others() {
+    let mut game = TestGame::new();
+    let mut first = connect(&mut game, 1);
+    game.start_match(PlayerType::Human, PlayerType::Human);
+
+    // One client never finishes its handshake, another never reads its frames
+    let address = game.app.world().resource::<SpectatorAddress>().0;
+    let mut half_open = TcpStream::connect(address).unwrap();
+    half_open.write_all(b"GET / HTTP/1.1\r\n").unwrap();
+    let _silent = join(&game);
+
+    frames(&mut game, &mut first, 10);
+    let mut late = join(&game);
+    let ticks: Vec<u64> = frames(&mut game, &mut late, 300).iter().map(|frame| frame.tick).collect();
+    assert!(ticks.windows(2).all(|pair| pair[1] == pair[0] + 1), "frames were dropped: {:?}", ticks);
+}
+
+#[test]
+fn spectators_over_the_limit_are_turned_away() {
+    let mut game = TestGame::new();
+    let _first = connect(&mut game, 1);
+    let address = game.app.world().resource::<SpectatorAddress>().0;
+    let url = format!("ws://{}", address);
+
+    let crowd: Vec<TcpStream> = (1..MAX_SPECTATORS).map(|_| TcpStream::connect(address).unwrap()).collect();
+    assert!(tungstenite::connect(&url).is_err(), "server accepted a spectator over the limit");
+
+    // Closed connections give their slots back
+    drop(crowd);
+    for _ in 0..100 {
+        if tungstenite::connect(&url).is_ok() {
+            return;
+        }
+        thread::sleep(Duration::from_millis(20));
+    }
+    panic!("slots were not freed");
+}
\ No newline at end of file
@@ -0,0 +1,23 @@
+use pong::game::drills::{Drill, DrillStorage};
+use pong::game::storage::DataDir;
+
+#[test]
+fn drills_are_saved_under_the_data_dir() {
+    let dir = std::env::temp_dir().join(format!("pong-storage-{}", std::process::id())).join("nested");
+    let storage = DrillStorage::new(&DataDir::Path(dir.clone()));
+
+    let mut presets = storage.load();
+    presets.save(Drill { name: "Lobs".to_string(), ..Drill::default() });
+    storage.save(&presets);
+
+    assert!(dir.join("drills.ron").exists());
+    assert!(storage.load().get("Lobs").is_some());
+
+    std::fs::remove_dir_all(dir.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn in_memory_storage_has_no_file() {
+    assert_eq!(DataDir::InMemory.file("drills.ron"), None);
+    assert_eq!(DataDir::Path("data".into()).file("drills.ron"), Some("data/drills.ron".into()));
+}
\ No newline at end of file
@@ -0,0 +1,50 @@
+use bevy::prelude::*;
+use crossterm::event::KeyCode as TermKey;
+use pong::terminal::{key_code, Grid};
+
+/// 10x5 cells over a 100x50 arena, so every cell is 10 units square.
+fn grid() -> Grid {
+    Grid::new(10, 5, Vec2::new(100., 50.))
+}
+
+#[test]
+fn cells_map_from_arena_coordinates() {
+    let grid = grid();
+    assert_eq!(grid.cell(Vec2::new(-50., 25.)), (0, 0));
+    assert_eq!(grid.cell(Vec2::ZERO), (5, 2));
+    assert_eq!(grid.cell(Vec2::new(49.9, -24.9)), (9, 4));
+    assert_eq!(grid.cell(Vec2::new(60., 30.)), (11, -1));
+}
+
+#[test]
+fn fill_is_clipped_at_the_edges() {
+    let mut grid = grid();
+    grid.fill(Vec2::new(45., 20.), Vec2::new(20., 10.), '#');
+
+    let lines: Vec<String> = grid.lines().collect();
+    assert_eq!(lines[0], "       ###");
+    assert_eq!(lines[1], "       ###");
+    assert_eq!(lines[2], "          ");
+}
+
+#[test]
+fn write_centres_and_cuts_text() {
+    let mut grid = grid();
+    grid.write(0, "abc");
+    grid.write(1, "far too long for the grid");
+
+    let lines: Vec<String> = grid.lines().collect();
+    assert_eq!(lines[0], "   abc    ");
+    assert_eq!(lines[1], "far too lo");
+}
+
+#[test]
+fn terminal_keys_map_to_bevy_keys() {
+    assert_eq!(key_code(TermKey::Up), Some(KeyCode::ArrowUp));
+    assert_eq!(key_code(TermKey::Esc), Some(KeyCode::Escape));
+    assert_eq!(key_code(TermKey::Char(' ')), Some(KeyCode::Space));
+    assert_eq!(key_code(TermKey::Char('w')), Some(KeyCode::KeyW));
+    assert_eq!(key_code(TermKey::Char('W')), Some(KeyCode::KeyW));
+    assert_eq!(key_code(TermKey::Char('1')), None);
+    assert_eq!(key_code(TermKey::F(1)), None);
+}
\ No newline at end of file